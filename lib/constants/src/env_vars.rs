@@ -9,12 +9,21 @@ impl EnvVars {
 
     pub const SKIP: &'static str = "SKIP";
 
+    pub const XDG_CACHE_HOME: &'static str = "XDG_CACHE_HOME";
+
     // PREK specific environment variables, public for users
     pub const PREK_HOME: &'static str = "PREK_HOME";
+    pub const PREK_CONFIG_HOME: &'static str = "PREK_CONFIG_HOME";
     pub const PREK_COLOR: &'static str = "PREK_COLOR";
     pub const PREK_ALLOW_NO_CONFIG: &'static str = "PREK_ALLOW_NO_CONFIG";
     pub const PREK_NO_CONCURRENCY: &'static str = "PREK_NO_CONCURRENCY";
     pub const PREK_NO_FAST_PATH: &'static str = "PREK_NO_FAST_PATH";
+    pub const PREK_PTY: &'static str = "PREK_PTY";
+    pub const PREK_OFFLINE: &'static str = "PREK_OFFLINE";
+    pub const PREK_NODE_MIRROR: &'static str = "PREK_NODE_MIRROR";
+    pub const PREK_GO_MIRROR: &'static str = "PREK_GO_MIRROR";
+    pub const PREK_UV_MIRROR: &'static str = "PREK_UV_MIRROR";
+    pub const PREK_PYTHON_MIRROR: &'static str = "PREK_PYTHON_MIRROR";
 
     // PREK internal environment variables
     pub const PREK_INTERNAL__TEST_DIR: &'static str = "PREK_INTERNAL__TEST_DIR";
@@ -23,7 +32,9 @@ impl EnvVars {
 
     // UV related
     pub const UV_CACHE_DIR: &'static str = "UV_CACHE_DIR";
+    pub const UV_OFFLINE: &'static str = "UV_OFFLINE";
     pub const UV_PYTHON_INSTALL_DIR: &'static str = "UV_PYTHON_INSTALL_DIR";
+    pub const UV_PYTHON_INSTALL_MIRROR: &'static str = "UV_PYTHON_INSTALL_MIRROR";
 
     // Node/Npm related
     pub const NPM_CONFIG_USERCONFIG: &'static str = "NPM_CONFIG_USERCONFIG";
@@ -35,12 +46,15 @@ impl EnvVars {
     pub const GOROOT: &'static str = "GOROOT";
     pub const GOPATH: &'static str = "GOPATH";
     pub const GOBIN: &'static str = "GOBIN";
+    pub const GOFLAGS: &'static str = "GOFLAGS";
+    pub const GOPROXY: &'static str = "GOPROXY";
 }
 
 impl EnvVars {
     // Pre-commit environment variables that we support for compatibility
     const PRE_COMMIT_ALLOW_NO_CONFIG: &'static str = "PRE_COMMIT_ALLOW_NO_CONFIG";
     const PRE_COMMIT_NO_CONCURRENCY: &'static str = "PRE_COMMIT_NO_CONCURRENCY";
+    pub const PRE_COMMIT_HOME: &'static str = "PRE_COMMIT_HOME";
 }
 
 impl EnvVars {
@@ -24,7 +24,7 @@ use std::ffi::OsString;
 use std::fmt::{Display, Formatter};
 use std::path::{Component, Path, PathBuf};
 
-use async_compression::tokio::bufread::{GzipDecoder, XzDecoder};
+use async_compression::tokio::bufread::{GzipDecoder, XzDecoder, ZstdDecoder};
 use async_zip::base::read::stream::ZipFileReader;
 use tokio::io::{AsyncRead, BufReader};
 use tokio_tar::ArchiveBuilder;
@@ -274,6 +274,25 @@ pub async fn untar_xz<R: AsyncRead + Unpin>(
     Ok(())
 }
 
+/// Unpack a `.tar.zst` archive into the target directory, without requiring `Seek`.
+///
+/// This is useful for unpacking files as they're being downloaded.
+pub async fn untar_zst<R: AsyncRead + Unpin>(
+    reader: R,
+    target: impl AsRef<Path>,
+) -> Result<(), Error> {
+    let reader = BufReader::with_capacity(DEFAULT_BUF_SIZE, reader);
+    let reader = ZstdDecoder::new(reader);
+
+    let mut archive = ArchiveBuilder::new(reader)
+        .set_preserve_mtime(true)
+        .set_preserve_permissions(true)
+        .build();
+
+    archive.unpack(target.as_ref()).await?;
+    Ok(())
+}
+
 /// Unpack a `.tar` archive into the target directory, without requiring `Seek`.
 ///
 /// This is useful for unpacking files as they're being downloaded.
@@ -301,6 +320,7 @@ pub async fn unpack<R: AsyncRead + Unpin>(
         ArchiveExtension::Tar => untar(reader, target).await,
         ArchiveExtension::TarGz => untar_gz(reader, target).await,
         ArchiveExtension::TarXz => untar_xz(reader, target).await,
+        ArchiveExtension::TarZst => untar_zst(reader, target).await,
         _ => Err(Error::UnsupportedArchive(target.as_ref().to_path_buf())),
     }
 }
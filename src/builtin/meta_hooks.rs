@@ -2,12 +2,11 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use fancy_regex::Regex;
 use itertools::Itertools;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use crate::cli::run::{CollectOptions, FileFilter, collect_files};
-use crate::config::{HookOptions, Language, Repo, read_config};
+use crate::config::{FilePattern, HookOptions, Language, Repo, read_config};
 use crate::hook::Hook;
 use crate::store::Store;
 use crate::workspace::Project;
@@ -17,7 +16,7 @@ pub(crate) async fn check_hooks_apply(
     _hook: &Hook,
     filenames: &[&String],
 ) -> Result<(i32, Vec<u8>)> {
-    let store = Store::from_settings()?.init()?;
+    let store = Store::from_settings()?.init().await?;
 
     let input = collect_files(CollectOptions::default().with_all_files(true)).await?;
 
@@ -25,14 +24,11 @@ pub(crate) async fn check_hooks_apply(
     let mut output = Vec::new();
 
     for filename in filenames {
-        let mut project = Project::from_config_file(Some(PathBuf::from(filename)))?;
+        let mut project =
+            Project::from_config_file(Some(PathBuf::from(filename)), &store, None).await?;
         let hooks = project.init_hooks(&store, None).await?;
 
-        let filter = FileFilter::new(
-            &input,
-            project.config().files.as_deref(),
-            project.config().exclude.as_deref(),
-        )?;
+        let filter = FileFilter::for_project(&input, &project)?;
 
         for hook in hooks {
             if hook.always_run || matches!(hook.language, Language::Fail) {
@@ -54,23 +50,23 @@ pub(crate) async fn check_hooks_apply(
 // Returns true if the exclude patter matches any files matching the include pattern.
 fn excludes_any<T: AsRef<str> + Sync>(
     files: &[T],
-    include: Option<&str>,
-    exclude: Option<&str>,
+    include: Option<&FilePattern>,
+    exclude: Option<&FilePattern>,
 ) -> Result<bool> {
-    if exclude.is_none_or(|s| s == "^$") {
+    if exclude.is_none_or(|pattern| *pattern == FilePattern::regex("^$")) {
         return Ok(true);
     }
-    let include = include.map(Regex::new).transpose()?;
-    let exclude = exclude.map(Regex::new).transpose()?;
+    let include = include.map(FilePattern::compile).transpose()?;
+    let exclude = exclude.map(FilePattern::compile).transpose()?;
     Ok(files.into_par_iter().any(|f| {
         let f = f.as_ref();
-        if let Some(re) = &include {
-            if !re.is_match(f).unwrap_or(false) {
+        if let Some(include) = &include {
+            if !include.is_match(f) {
                 return false;
             }
         }
-        if let Some(re) = &exclude {
-            if !re.is_match(f).unwrap_or(false) {
+        if let Some(exclude) = &exclude {
+            if !exclude.is_match(f) {
                 return false;
             }
         }
@@ -91,16 +87,20 @@ pub(crate) async fn check_useless_excludes(
     for filename in filenames {
         let config = read_config(Path::new(filename))?;
 
-        if !excludes_any(&input, None, config.exclude.as_deref())? {
+        if !excludes_any(&input, None, config.exclude.as_ref())? {
             code = 1;
             writeln!(
                 &mut output,
                 "The global exclude pattern {:?} does not match any files",
-                config.exclude.as_deref().unwrap_or("")
+                config
+                    .exclude
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default()
             )?;
         }
 
-        let filter = FileFilter::new(&input, config.files.as_deref(), config.exclude.as_deref())?;
+        let filter = FileFilter::new(&input, config.files.as_ref(), config.exclude.as_ref())?;
 
         let hooks = config.repos.iter().flat_map(
             |repo| -> Box<dyn Iterator<Item = (&String, &HookOptions)>> {
@@ -119,16 +119,15 @@ pub(crate) async fn check_useless_excludes(
                 opts.types_or.as_deref().unwrap_or(&[]),
                 opts.exclude_types.as_deref().unwrap_or(&[]),
             );
-            if !excludes_any(
-                &filtered_files,
-                opts.files.as_deref(),
-                opts.exclude.as_deref(),
-            )? {
+            if !excludes_any(&filtered_files, opts.files.as_ref(), opts.exclude.as_ref())? {
                 code = 1;
                 writeln!(
                     &mut output,
                     "The exclude pattern `{}` for `{hook_id}` does not match any files",
-                    opts.exclude.as_deref().unwrap_or(""),
+                    opts.exclude
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
                 )?;
             }
         }
@@ -148,13 +147,27 @@ mod tests {
 
     #[test]
     fn test_excludes_any() -> Result<()> {
+        let regex = FilePattern::regex;
         let files = vec!["file1.txt", "file2.txt", "file3.txt"];
-        assert!(excludes_any(&files, Some("file.*"), Some("file2.txt"))?);
-        assert!(!excludes_any(&files, Some("file.*"), Some("file4.txt"))?);
+        assert!(excludes_any(
+            &files,
+            Some(&regex("file.*")),
+            Some(&regex("file2.txt"))
+        )?);
+        assert!(!excludes_any(
+            &files,
+            Some(&regex("file.*")),
+            Some(&regex("file4.txt"))
+        )?);
         assert!(excludes_any(&files, None, None)?);
 
         let files = vec!["html/file1.html", "html/file2.html"];
-        assert!(excludes_any(&files, None, Some("^html/"))?);
+        assert!(excludes_any(&files, None, Some(&regex("^html/")))?);
+
+        let glob = FilePattern::Glob {
+            glob: vec!["html/*.html".to_string()],
+        };
+        assert!(excludes_any(&files, None, Some(&glob))?);
         Ok(())
     }
 }
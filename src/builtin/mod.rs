@@ -5,11 +5,14 @@ use constants::env_vars::EnvVars;
 
 use crate::builtin::pre_commit_hooks::{Implemented, is_pre_commit_hooks};
 use crate::hook::{Hook, Repo};
+use crate::settings::Settings;
 
 mod meta_hooks;
 mod pre_commit_hooks;
 
-static NO_FAST_PATH: LazyLock<bool> = LazyLock::new(|| EnvVars::is_set(EnvVars::PREK_NO_FAST_PATH));
+static NO_FAST_PATH: LazyLock<bool> = LazyLock::new(|| {
+    EnvVars::is_set(EnvVars::PREK_NO_FAST_PATH) || Settings::get().fast_path == Some(false)
+});
 
 /// Returns true if the hook has a builtin Rust implementation.
 pub fn check_fast_path(hook: &Hook) -> bool {
@@ -26,6 +29,25 @@ pub fn check_fast_path(hook: &Hook) -> bool {
 }
 
 pub async fn run_fast_path(hook: &Hook, filenames: &[&String]) -> anyhow::Result<(i32, Vec<u8>)> {
+    // Builtin hooks run in the workspace root, make the filenames relative to it.
+    let joined: Vec<String>;
+    let joined_refs: Vec<&String>;
+    let filenames = if hook.project_dir().as_os_str().is_empty() {
+        filenames
+    } else {
+        joined = filenames
+            .iter()
+            .map(|filename| {
+                hook.project_dir()
+                    .join(filename)
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        joined_refs = joined.iter().collect();
+        &joined_refs
+    };
+
     match hook.repo() {
         Repo::Meta { .. } => run_meta_hook(hook, filenames).await,
         Repo::Remote { url, .. } if is_pre_commit_hooks(url) => {
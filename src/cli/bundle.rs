@@ -0,0 +1,189 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_compression::tokio::write::ZstdEncoder;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, trace};
+
+use crate::archive::{self, ArchiveExtension};
+use crate::cli::reporter::{HookInitReporter, HookInstallReporter};
+use crate::cli::{ExitStatus, run};
+use crate::fs::Simplified;
+use crate::hook::{InstalledHook, Repo};
+use crate::printer::Printer;
+use crate::store::{Store, relocate};
+use crate::workspace::Project;
+
+/// The manifest of a bundle, the first entry of the archive.
+const MANIFEST: &str = "prek-bundle.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    /// The store path on the machine the bundle was exported from.
+    home: PathBuf,
+    /// The repos, hook environments and toolchains in the bundle, relative to the store.
+    entries: Vec<PathBuf>,
+}
+
+pub(crate) async fn bundle_export(
+    config: Option<PathBuf>,
+    file: &Path,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Bundles are always written as zstd compressed tarballs.
+    if !file.to_string_lossy().ends_with(".tar.zst") {
+        anyhow::bail!(
+            "Bundle file `{}` must have the `.tar.zst` extension",
+            file.user_display()
+        );
+    }
+
+    let store = Store::from_settings()?.init().await?;
+    let _lock = store.lock_shared_async().await?;
+    let home = std::path::absolute(store.path())?;
+
+    let reporter = HookInitReporter::from(printer);
+    let mut project = Project::from_config_file(config, &store, Some(&reporter)).await?;
+
+    // Make sure everything the config needs is in the store.
+    let reporter = HookInitReporter::from(printer);
+    let hooks = project.init_hooks(&store, Some(&reporter)).await?;
+    let reporter = HookInstallReporter::from(printer);
+    let hooks = run::install_hooks(hooks, &store, &reporter).await?;
+
+    let manifest = Manifest {
+        entries: bundle_entries(&store, &home, &hooks).into_iter().collect(),
+        home,
+    };
+
+    let mut builder =
+        tokio_tar::Builder::new(ZstdEncoder::new(fs_err::tokio::File::create(file).await?));
+    // Keep the symlinks of environments, they are relocated on import.
+    builder.follow_symlinks(false);
+
+    let content = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = tokio_tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    builder
+        .append_data(&mut header, MANIFEST, content.as_slice())
+        .await?;
+
+    for entry in &manifest.entries {
+        let path = manifest.home.join(entry);
+        trace!(path = %path.display(), "Adding to bundle");
+        if path.is_dir() {
+            builder.append_dir_all(entry, &path).await?;
+        } else {
+            builder.append_path_with_name(&path, entry).await?;
+        }
+    }
+    builder.into_inner().await?.shutdown().await?;
+
+    writeln!(
+        printer.stdout(),
+        "Exported {} to `{}`",
+        entries(manifest.entries.len()).bold(),
+        file.user_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+fn entries(count: usize) -> String {
+    if count == 1 {
+        "1 entry".to_string()
+    } else {
+        format!("{count} entries")
+    }
+}
+
+/// The cloned repos, hook environments and toolchains in the store used by the hooks.
+fn bundle_entries(store: &Store, home: &Path, hooks: &[InstalledHook]) -> BTreeSet<PathBuf> {
+    let mut entries = BTreeSet::new();
+    for hook in hooks {
+        if let Repo::Remote { path, url, rev, .. } = hook.repo() {
+            // `repos/<repo>`
+            entries.extend(store_entry(home, path, 2));
+            // `refs/<rev>`, unless the rev is a commit.
+            let rev_path = store.rev_path(url.as_str(), rev);
+            if rev_path.is_file() {
+                entries.extend(store_entry(home, &rev_path, 2));
+            }
+        }
+        if let InstalledHook::Installed { info, .. } = hook {
+            // `hooks/<env>`
+            entries.extend(store_entry(home, &info.env_path, 2));
+            // `tools/<tool>/<version>`, toolchains found on the system are not bundled.
+            entries.extend(store_entry(home, &info.toolchain, 3));
+        }
+    }
+    entries
+}
+
+/// The first `depth` components of `path` relative to the store, if it is in the store.
+fn store_entry(home: &Path, path: &Path, depth: usize) -> Option<PathBuf> {
+    let relative = std::path::absolute(path).ok()?;
+    let relative = relative.strip_prefix(home).ok()?;
+    let entry = relative.components().take(depth).collect::<PathBuf>();
+    (entry.components().count() == depth).then_some(entry)
+}
+
+pub(crate) async fn bundle_import(file: &Path, printer: Printer) -> Result<ExitStatus> {
+    let store = Store::from_settings()?.init().await?;
+    let _lock = store.lock_async().await?;
+    let home = std::path::absolute(store.path())?;
+
+    let temp_dir = tempfile::tempdir_in(&home)?;
+    let ext = ArchiveExtension::from_path(file)?;
+    let reader = fs_err::tokio::File::open(file).await?;
+    archive::unpack(reader, ext, temp_dir.path())
+        .await
+        .with_context(|| format!("Failed to unpack bundle `{}`", file.user_display()))?;
+
+    let manifest = fs_err::read(temp_dir.path().join(MANIFEST))
+        .with_context(|| format!("`{}` is not a prek bundle", file.user_display()))?;
+    let manifest: Manifest = serde_json::from_slice(&manifest)
+        .with_context(|| format!("Failed to parse the manifest of `{}`", file.user_display()))?;
+
+    let mut imported = 0;
+    for entry in &manifest.entries {
+        if !entry
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            anyhow::bail!("Invalid entry `{}` in bundle", entry.display());
+        }
+
+        let source = temp_dir.path().join(entry);
+        let target = home.join(entry);
+        if target.try_exists()? {
+            debug!(entry = %entry.display(), "Already in the store, skipping");
+            continue;
+        }
+
+        // Hook environments may have the store path in scripts and config files.
+        let rewrite_files = entry.starts_with("hooks");
+        relocate(&source, &manifest.home, &home, rewrite_files)
+            .with_context(|| format!("Failed to relocate `{}`", entry.display()))?;
+
+        if let Some(parent) = target.parent() {
+            fs_err::tokio::create_dir_all(parent).await?;
+        }
+        fs_err::tokio::rename(&source, &target).await?;
+        imported += 1;
+    }
+
+    writeln!(
+        printer.stdout(),
+        "Imported {} into `{}`",
+        entries(imported).bold(),
+        home.user_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
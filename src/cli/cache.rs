@@ -0,0 +1,343 @@
+use std::fmt::Write;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+use url::Url;
+
+use constants::env_vars::EnvVars;
+
+use crate::cli::ExitStatus;
+use crate::config::{RemoteRepo, UnknownKeys};
+use crate::fs::Simplified;
+use crate::printer::Printer;
+use crate::store::{CacheBucket, Store, ToolBucket};
+use crate::warn_user;
+
+pub(crate) fn cache_dir(printer: Printer) -> Result<ExitStatus> {
+    let store = Store::from_settings()?;
+    writeln!(
+        printer.stdout(),
+        "{}",
+        store.path().simplified_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+pub(crate) fn cache_size(printer: Printer) -> Result<ExitStatus> {
+    let store = Store::from_settings()?;
+
+    let mut buckets = vec![
+        ("repos".to_string(), store.repos_dir()),
+        ("hooks".to_string(), store.hooks_dir()),
+    ];
+    for tool in ToolBucket::ALL {
+        buckets.push((format!("tools/{}", tool.as_str()), store.tools_path(tool)));
+    }
+    for cache in CacheBucket::ALL {
+        buckets.push((format!("cache/{}", cache.as_str()), store.cache_path(cache)));
+    }
+
+    for (name, path) in buckets {
+        if path.exists() {
+            writeln!(
+                printer.stdout(),
+                "{name:<12}{}",
+                human_readable_bytes(dir_size(&path))
+            )?;
+        }
+    }
+    writeln!(
+        printer.stdout(),
+        "{:<12}{}",
+        "Total".bold(),
+        human_readable_bytes(dir_size(store.path())).bold()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+pub(crate) fn cache_list(printer: Printer) -> Result<ExitStatus> {
+    let store = Store::from_settings()?;
+
+    let mut envs = store.installed_hooks().collect::<Vec<_>>();
+    if envs.is_empty() {
+        writeln!(printer.stdout(), "No hook environments installed")?;
+        return Ok(ExitStatus::Success);
+    }
+    envs.sort_unstable_by(|a, b| a.env_path.cmp(&b.env_path));
+
+    let now = SystemTime::now();
+    for info in envs {
+        let name = info
+            .env_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy();
+        writeln!(
+            printer.stdout(),
+            "{} ({} {})",
+            name.bold(),
+            info.language.as_str(),
+            info.language_version
+        )?;
+        let mut dependencies = info.dependencies.iter().collect::<Vec<_>>();
+        if !dependencies.is_empty() {
+            dependencies.sort_unstable();
+            writeln!(
+                printer.stdout(),
+                "  dependencies: {}",
+                dependencies.into_iter().join(", ")
+            )?;
+        }
+        writeln!(printer.stdout(), "  path: {}", info.env_path.user_display())?;
+        if let Some(last_used) = info.last_used() {
+            let elapsed = now.duration_since(last_used).unwrap_or_default();
+            writeln!(printer.stdout(), "  last used: {}", format_elapsed(elapsed))?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+pub(crate) async fn cache_remove(entries: &[String], printer: Printer) -> Result<ExitStatus> {
+    let store = Store::from_settings()?;
+    let _lock = if store.path().exists() {
+        Some(store.lock_async().await?)
+    } else {
+        None
+    };
+
+    let mut status = ExitStatus::Success;
+    for entry in entries {
+        let paths = matching_paths(&store, entry);
+        if paths.is_empty() {
+            writeln!(
+                printer.stderr(),
+                "{}{} No cached repo, hook environment or toolchain matches `{}`",
+                "error".red().bold(),
+                ":".bold(),
+                entry.cyan()
+            )?;
+            status = ExitStatus::Failure;
+            continue;
+        }
+
+        for path in paths {
+            fs_err::tokio::remove_dir_all(&path).await?;
+            writeln!(printer.stdout(), "Removed `{}`", path.user_display().cyan())?;
+        }
+    }
+
+    Ok(status)
+}
+
+/// The store paths matching an entry of `prek cache remove`.
+fn matching_paths(store: &Store, entry: &str) -> Vec<PathBuf> {
+    // A toolchain, `<tool>` or `<tool>/<version>`.
+    let (tool, version) = entry
+        .split_once('/')
+        .map_or((entry, None), |(tool, version)| (tool, Some(version)));
+    if let Some(tool) = ToolBucket::ALL.into_iter().find(|t| t.as_str() == tool) {
+        let path = match version {
+            Some(version) if is_file_name(version) => store.tools_path(tool).join(version),
+            Some(_) => return vec![],
+            None => store.tools_path(tool),
+        };
+        return path.is_dir().then_some(path).into_iter().collect();
+    }
+
+    // A hook environment.
+    if is_file_name(entry) {
+        let path = store.hooks_dir().join(entry);
+        if path.is_dir() {
+            return vec![path];
+        }
+    }
+
+    // A repo, all of its clones.
+    let url = Url::parse(entry).map_or_else(|_| entry.to_string(), String::from);
+    store
+        .cloned_repos()
+        .filter(|(_, info)| info.repo == url)
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// Whether `name` is a single path component, that can't escape the store.
+fn is_file_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    )
+}
+
+/// The total size of the files under `path`, without following symlinks.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs_err::symlink_metadata(path) else {
+        return 0;
+    };
+    if metadata.is_dir() {
+        fs_err::read_dir(path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| dir_size(&entry.path()))
+            .sum()
+    } else {
+        metadata.len()
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn human_readable_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes}B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1}{}", UNITS[unit])
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (count, unit) = match secs {
+        0..60 => return "just now".to_string(),
+        60..3600 => (secs / 60, "minute"),
+        3600..86400 => (secs / 3600, "hour"),
+        _ => (secs / 86400, "day"),
+    };
+    if count == 1 {
+        format!("1 {unit} ago")
+    } else {
+        format!("{count} {unit}s ago")
+    }
+}
+
+/// A repo cloned by pre-commit, as recorded in its `db.db`.
+struct PreCommitRepo {
+    repo: String,
+    rev: String,
+    path: PathBuf,
+}
+
+pub(crate) async fn cache_import_pre_commit(
+    dir: Option<PathBuf>,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let dir = dir
+        .or_else(pre_commit_home)
+        .context("Failed to find the pre-commit cache directory")?;
+    let db = dir.join("db.db");
+    if !db.is_file() {
+        writeln!(
+            printer.stdout(),
+            "No pre-commit cache found at `{}`",
+            dir.user_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let repos = read_pre_commit_db(&db)
+        .with_context(|| format!("Failed to read `{}`", db.user_display()))?;
+
+    let store = Store::from_settings()?.init().await?;
+    let _lock = store.lock_shared_async().await?;
+
+    let mut imported = 0;
+    for PreCommitRepo { repo, rev, path } in repos {
+        // `local` repos are created by pre-commit to install dependencies of local hooks.
+        if repo == "local" || repo.starts_with("local:") {
+            continue;
+        }
+        let repo = strip_dependencies(&repo);
+        let Ok(url) = Url::parse(repo) else {
+            warn_user!("Skipping `{repo}`, it is not a valid repo URL");
+            continue;
+        };
+        if !path.is_dir() {
+            debug!(path = %path.display(), "Clone of `{repo}@{rev}` not found, skipping");
+            continue;
+        }
+
+        let remote = RemoteRepo {
+            repo: url,
+            rev,
+            hooks: vec![],
+            unknown: UnknownKeys::default(),
+        };
+        match store.import_repo(&remote, &path).await {
+            Ok(Some(_)) => imported += 1,
+            Ok(None) => debug!(%remote, "Already in the store, skipping"),
+            Err(err) => {
+                warn_user!("Failed to import `{remote}`: {err}");
+            }
+        }
+    }
+
+    writeln!(
+        printer.stdout(),
+        "Imported {} from `{}`",
+        if imported == 1 {
+            "1 repo".to_string()
+        } else {
+            format!("{imported} repos")
+        }
+        .bold(),
+        dir.user_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Strip the `:<dep1>,<dep2>` suffix pre-commit adds to the repo of a clone made for hooks
+/// with `additional_dependencies`, the clone itself is a plain clone of the repo.
+fn strip_dependencies(repo: &str) -> &str {
+    let Some((scheme, rest)) = repo.split_once("://") else {
+        return repo;
+    };
+    // A `:` after the start of the path can't be part of the URL of a git repo.
+    let path = rest.find('/').unwrap_or(rest.len());
+    match rest[path..].find(':') {
+        Some(idx) => &repo[..scheme.len() + "://".len() + path + idx],
+        None => repo,
+    }
+}
+
+/// The pre-commit cache directory, resolved the way pre-commit does.
+fn pre_commit_home() -> Option<PathBuf> {
+    if let Some(home) = EnvVars::var_os(EnvVars::PRE_COMMIT_HOME) {
+        return Some(PathBuf::from(home));
+    }
+    let cache = EnvVars::var_os(EnvVars::XDG_CACHE_HOME)
+        .map(PathBuf::from)
+        .or_else(|| etcetera::home_dir().ok().map(|home| home.join(".cache")))?;
+    Some(cache.join("pre-commit"))
+}
+
+fn read_pre_commit_db(db: &Path) -> Result<Vec<PreCommitRepo>> {
+    let conn =
+        rusqlite::Connection::open_with_flags(db, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare("SELECT repo, ref, path FROM repos")?;
+    let repos = stmt
+        .query_map([], |row| {
+            Ok(PreCommitRepo {
+                repo: row.get(0)?,
+                rev: row.get(1)?,
+                path: PathBuf::from(row.get::<_, String>(2)?),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(repos)
+}
@@ -0,0 +1,69 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use crate::cli::ExitStatus;
+use crate::config::{ConfigFormat, read_config};
+use crate::fs::Simplified;
+use crate::printer::Printer;
+
+/// Convert a YAML config to TOML, or a TOML config to YAML.
+///
+/// Comments and formatting of the original file are not preserved.
+#[allow(clippy::print_stdout)]
+pub(crate) fn convert_config(
+    input: &Path,
+    output: Option<PathBuf>,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Make sure the config is valid before converting it.
+    read_config(input)?;
+
+    let content = fs_err::read_to_string(input)?;
+    let converted = match ConfigFormat::from_path(input) {
+        ConfigFormat::Yaml => {
+            let mut value: serde_yaml::Value = serde_yaml::from_str(&content)?;
+            remove_nulls(&mut value);
+            toml::to_string_pretty(&value)?
+        }
+        ConfigFormat::Toml => {
+            let value: toml::Value = toml::from_str(&content)?;
+            serde_yaml::to_string(&value)?
+        }
+    };
+
+    if let Some(output) = output {
+        if output.exists() {
+            anyhow::bail!(
+                "File `{}` already exists",
+                output.simplified_display().cyan()
+            );
+        }
+        fs_err::write(&output, converted)?;
+
+        writeln!(
+            printer.stdout(),
+            "Written to `{}`",
+            output.simplified_display().cyan()
+        )?;
+
+        return Ok(ExitStatus::Success);
+    }
+
+    print!("{converted}");
+    Ok(ExitStatus::Success)
+}
+
+/// TOML has no null value, drop the keys set to null.
+fn remove_nulls(value: &mut serde_yaml::Value) {
+    match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            mapping.retain(|_, value| !value.is_null());
+            mapping.values_mut().for_each(remove_nulls);
+        }
+        serde_yaml::Value::Sequence(sequence) => sequence.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use crate::cli::ExitStatus;
+use crate::cli::reporter::HookInitReporter;
+use crate::fs::Simplified;
+use crate::languages::EntryNotFound;
+use crate::printer::Printer;
+use crate::store::Store;
+use crate::workspace::Project;
+
+/// Check the health of the environments of the hooks in the config.
+pub(crate) async fn doctor(config: Option<PathBuf>, printer: Printer) -> Result<ExitStatus> {
+    let store = Store::from_settings()?.init().await?;
+    let _lock = store.lock_shared_async().await?;
+
+    let reporter = HookInitReporter::from(printer);
+    let mut project = Project::from_config_file(config, &store, Some(&reporter)).await?;
+    let reporter = HookInitReporter::from(printer);
+    let hooks = project.init_hooks(&store, Some(&reporter)).await?;
+    let installed_hooks = store.installed_hooks().collect::<Vec<_>>();
+
+    let mut broken = 0;
+    for hook in &hooks {
+        if !hook.language.supports_install_env() {
+            continue;
+        }
+
+        let mut envs = installed_hooks
+            .iter()
+            .filter(|info| info.matches(hook))
+            .peekable();
+        if envs.peek().is_none() {
+            writeln!(
+                printer.stdout(),
+                "{}: {}",
+                hook.to_string().bold(),
+                "not installed".dimmed()
+            )?;
+            continue;
+        }
+
+        for info in envs {
+            match hook.language.check_health(hook, info).await {
+                Ok(()) => writeln!(
+                    printer.stdout(),
+                    "{}: {} `{}`",
+                    hook.to_string().bold(),
+                    "healthy".green(),
+                    info.env_path.user_display().cyan()
+                )?,
+                Err(err) if err.is::<EntryNotFound>() => writeln!(
+                    printer.stdout(),
+                    "{}: {} `{}`\n  {err}",
+                    hook.to_string().bold(),
+                    "warning".yellow(),
+                    info.env_path.user_display().cyan()
+                )?,
+                Err(err) => {
+                    broken += 1;
+                    writeln!(
+                        printer.stdout(),
+                        "{}: {} `{}`\n  {err}",
+                        hook.to_string().bold(),
+                        "broken".red(),
+                        info.env_path.user_display().cyan()
+                    )?;
+                }
+            }
+        }
+    }
+
+    if broken > 0 {
+        writeln!(
+            printer.stdout(),
+            "\nFound {} broken environment(s), run `{}` to rebuild them",
+            broken.to_string().bold(),
+            "prek install-hooks".cyan()
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    Ok(ExitStatus::Success)
+}
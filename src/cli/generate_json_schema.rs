@@ -0,0 +1,17 @@
+use anyhow::Result;
+use schemars::schema_for;
+
+use crate::cli::{ExitStatus, SchemaKind};
+use crate::config::{Config, Manifest};
+
+/// Print the JSON schema of config or manifest files, generated from the config types.
+#[allow(clippy::print_stdout)]
+pub(crate) fn generate_json_schema(kind: SchemaKind) -> Result<ExitStatus> {
+    let schema = match kind {
+        SchemaKind::Config => schema_for!(Config),
+        SchemaKind::Manifest => schema_for!(Manifest),
+    };
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+
+    Ok(ExitStatus::Success)
+}
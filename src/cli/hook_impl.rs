@@ -6,7 +6,7 @@ use anstream::eprintln;
 
 use constants::env_vars::EnvVars;
 
-use crate::cli::{self, ExitStatus, RunArgs};
+use crate::cli::{self, ExitStatus, HookSelector, RunArgs};
 use crate::config::HookType;
 use crate::printer::Printer;
 
@@ -48,7 +48,7 @@ pub(crate) async fn hook_impl(
 
     cli::run(
         config,
-        run_args.hook_id,
+        HookSelector::from_args(&run_args),
         hook_type.into(),
         run_args.from_ref,
         run_args.to_ref,
@@ -57,6 +57,8 @@ pub(crate) async fn hook_impl(
         vec![],
         false, // last_commit is always false in hook implementation context
         false,
+        false,
+        None,
         run_args.extra,
         false,
         printer,
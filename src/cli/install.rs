@@ -37,7 +37,13 @@ pub(crate) async fn install(
         return Ok(ExitStatus::Failure);
     }
 
-    let project = Project::from_config_file(config.clone()).ok();
+    let store = Store::from_settings()?.init().await?;
+    let lock = store.lock_shared_async().await?;
+    let reporter = HookInitReporter::from(printer);
+    let project = Project::from_config_file(config.clone(), &store, Some(&reporter))
+        .await
+        .ok();
+    drop(lock);
     let hook_types = get_hook_types(project.as_ref(), hook_types);
 
     let hooks_path = if let Some(dir) = git_dir {
@@ -66,10 +72,11 @@ pub(crate) async fn install(
 }
 
 pub(crate) async fn install_hooks(config: Option<PathBuf>, printer: Printer) -> Result<ExitStatus> {
-    let mut project = Project::from_config_file(config)?;
-    let store = Store::from_settings()?.init()?;
-    let _lock = store.lock_async().await?;
+    let store = Store::from_settings()?.init().await?;
+    let _lock = store.lock_shared_async().await?;
 
+    let reporter = HookInitReporter::from(printer);
+    let mut project = Project::from_config_file(config, &store, Some(&reporter)).await?;
     let reporter = HookInitReporter::from(printer);
     let hooks = project.init_hooks(&store, Some(&reporter)).await?;
     let reporter = HookInstallReporter::from(printer);
@@ -208,7 +215,13 @@ pub(crate) async fn uninstall(
     hook_types: Vec<HookType>,
     printer: Printer,
 ) -> Result<ExitStatus> {
-    let project = Project::from_config_file(config).ok();
+    let store = Store::from_settings()?.init().await?;
+    let lock = store.lock_shared_async().await?;
+    let reporter = HookInitReporter::from(printer);
+    let project = Project::from_config_file(config, &store, Some(&reporter))
+        .await
+        .ok();
+    drop(lock);
     for hook_type in get_hook_types(project.as_ref(), hook_types) {
         let hooks_path = git::get_git_common_dir().await?.join("hooks");
         let hook_path = hooks_path.join(hook_type.as_str());
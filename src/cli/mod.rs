@@ -6,13 +6,21 @@ use clap::builder::styling::{AnsiColor, Effects};
 use clap::builder::{StyledStr, Styles};
 use clap::{ArgAction, Args, Parser, Subcommand, ValueHint};
 use clap_complete::engine::{ArgValueCompleter, CompletionCandidate};
+use serde::Deserialize;
 
 use constants::env_vars::EnvVars;
 
-use crate::config::{self, CONFIG_FILE, HookType, Stage};
+use crate::config::{self, ConfigFormat, HookType, Language, Stage};
+use crate::offline;
+use crate::store::Store;
 use crate::workspace::Project;
 
+mod bundle;
+mod cache;
 mod clean;
+mod convert_config;
+mod doctor;
+mod generate_json_schema;
 mod hook_impl;
 mod install;
 mod reporter;
@@ -21,10 +29,15 @@ mod sample_config;
 mod self_update;
 mod validate;
 
+pub(crate) use bundle::{bundle_export, bundle_import};
+pub(crate) use cache::{cache_dir, cache_import_pre_commit, cache_list, cache_remove, cache_size};
 pub(crate) use clean::clean;
+pub(crate) use convert_config::convert_config;
+pub(crate) use doctor::doctor;
+pub(crate) use generate_json_schema::generate_json_schema;
 pub(crate) use hook_impl::hook_impl;
 pub(crate) use install::{init_template_dir, install, install_hooks, uninstall};
-pub(crate) use run::run;
+pub(crate) use run::{HookSelector, run};
 pub(crate) use sample_config::sample_config;
 pub(crate) use self_update::self_update;
 pub(crate) use validate::{validate_configs, validate_manifest};
@@ -43,7 +56,13 @@ fn get_hook_id_candidates(current: &std::ffi::OsStr) -> anyhow::Result<Vec<Compl
     let root = String::from_utf8(output.stdout)?.trim().to_string();
     std::env::set_current_dir(&root).ok();
 
-    let project = Project::from_config_file(None)?;
+    // Completions never access the network, only base configs already in the store are used.
+    offline::enable();
+    let store = Store::from_settings()?;
+    let project = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?
+        .block_on(Project::from_config_file(None, &store, None))?;
 
     let hook_ids = project
         .config()
@@ -108,9 +127,11 @@ impl From<ExitStatus> for ExitCode {
     }
 }
 
-#[derive(Debug, Copy, Clone, clap::ValueEnum)]
+#[derive(Debug, Copy, Clone, Default, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
 pub enum ColorChoice {
     /// Enables colored output only when the output is going to a terminal or TTY with support.
+    #[default]
     Auto,
 
     /// Enables colored output regardless of the detected environment.
@@ -162,6 +183,7 @@ pub(crate) struct Cli {
     pub(crate) globals: GlobalArgs,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Parser)]
 #[command(next_help_heading = "Global options", next_display_order = 1000)]
 #[command(disable_help_flag = true, disable_version_flag = true)]
@@ -171,14 +193,10 @@ pub(crate) struct GlobalArgs {
     pub(crate) config: Option<PathBuf>,
 
     /// Whether to use color in output.
-    #[arg(
-        global = true,
-        long,
-        value_enum,
-        env = EnvVars::PREK_COLOR,
-        default_value_t = ColorChoice::Auto,
-    )]
-    pub(crate) color: ColorChoice,
+    ///
+    /// Defaults to `auto`.
+    #[arg(global = true, long, value_enum, env = EnvVars::PREK_COLOR)]
+    pub(crate) color: Option<ColorChoice>,
 
     /// Display the concise help for this command.
     #[arg(global = true, short, long, action = clap::ArgAction::HelpShort)]
@@ -190,6 +208,13 @@ pub(crate) struct GlobalArgs {
     #[arg(global = true, long)]
     pub no_progress: bool,
 
+    /// Never access the network.
+    ///
+    /// Repos, toolchains and hook dependencies are only resolved from the store or the system,
+    /// and prek fails with a list of everything that is missing.
+    #[arg(global = true, long, env = EnvVars::PREK_OFFLINE, value_parser = clap::builder::BoolishValueParser::new())]
+    pub(crate) offline: bool,
+
     /// Do not print any output.
     #[arg(global = true, long, short, conflicts_with = "verbose")]
     pub quiet: bool,
@@ -225,8 +250,10 @@ pub(crate) enum Command {
     ValidateConfig(ValidateConfigArgs),
     /// Validate `.pre-commit-hooks.yaml` files.
     ValidateManifest(ValidateManifestArgs),
-    /// Produce a sample `.pre-commit-config.yaml` file.
+    /// Produce a sample `.pre-commit-config.yaml` or `prek.toml` file.
     SampleConfig(SampleConfigArgs),
+    /// Convert a config file between the YAML and TOML formats.
+    ConvertConfig(ConvertConfigArgs),
     /// Auto-update pre-commit config to the latest repos' versions.
     #[command(name = "auto-update", alias = "autoupdate")]
     AutoUpdate(AutoUpdateArgs),
@@ -239,6 +266,12 @@ pub(crate) enum Command {
     InitTemplateDir(InitTemplateDirArgs),
     /// Try the pre-commit hooks in the current repo.
     TryRepo(Box<RunArgs>),
+    /// Export or import the repos, hook environments and toolchains of a config.
+    Bundle(BundleNamespace),
+    /// Manage the prek cache.
+    Cache(CacheNamespace),
+    /// Check the health of the hook environments.
+    Doctor,
 
     /// The implementation of the `pre-commit` hook.
     #[command(hide = true)]
@@ -251,6 +284,10 @@ pub(crate) enum Command {
     /// Generate shell completion scripts.
     #[command(hide = true)]
     GenerateShellCompletion(GenerateShellCompletionArgs),
+
+    /// Generate the JSON schema of config or manifest files.
+    #[command(hide = true)]
+    GenerateJsonSchema(GenerateJsonSchemaArgs),
 }
 
 #[derive(Debug, Args)]
@@ -305,11 +342,31 @@ pub(crate) struct RunExtraArgs {
     pub(crate) rewrite_command: Option<String>,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Default, Args)]
 pub(crate) struct RunArgs {
-    /// The hook ID to run.
+    /// The hook IDs to run.
     #[arg(value_name = "HOOK", value_hint = ValueHint::Other, add = ArgValueCompleter::new(hook_id_completer))]
-    pub(crate) hook_id: Option<String>,
+    pub(crate) hook_ids: Vec<String>,
+    /// Run hooks with this tag, can be used multiple times.
+    #[arg(long, value_name = "TAG", value_hint = ValueHint::Other)]
+    pub(crate) tag: Vec<String>,
+    /// Run hooks in the group defined in the `groups` config, can be used multiple times.
+    #[arg(long, value_name = "NAME", value_hint = ValueHint::Other)]
+    pub(crate) group: Vec<String>,
+    /// Skip the hook with this ID, can be used multiple times.
+    ///
+    /// Hooks listed in the `SKIP` environment variable are also skipped.
+    #[arg(long, value_name = "HOOK", value_hint = ValueHint::Other, add = ArgValueCompleter::new(hook_id_completer))]
+    pub(crate) skip: Vec<String>,
+    /// Only run hooks from the repo with this URL, can be used multiple times.
+    ///
+    /// Use `local` or `meta` to select hooks from the local or meta repo.
+    #[arg(long, value_name = "URL", value_hint = ValueHint::Url)]
+    pub(crate) repo: Vec<String>,
+    /// Only run hooks of this language, can be used multiple times.
+    #[arg(long, value_enum)]
+    pub(crate) language: Vec<Language>,
     /// Run on all files in the repo.
     #[arg(short, long, conflicts_with_all = ["files", "from_ref", "to_ref"])]
     pub(crate) all_files: bool,
@@ -338,6 +395,19 @@ pub(crate) struct RunArgs {
     /// When hooks fail, run `git diff` directly afterward.
     #[arg(long)]
     pub(crate) show_diff_on_failure: bool,
+    /// Show which hooks would run on which files, without installing or running them. Remote
+    /// repos are still cloned, to read the hooks they define.
+    ///
+    /// The commands shown are the entry, args and files of each hook, hooks that run in a
+    /// container or in an environment say so.
+    #[arg(long)]
+    pub(crate) dry_run: bool,
+    /// Only run the hooks of the project in this directory.
+    ///
+    /// Every directory with a config file in the repository is a project. Can't be used with a
+    /// `--config` outside the root of the repository.
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub(crate) cd: Option<PathBuf>,
 
     #[command(flatten)]
     pub(crate) extra: RunExtraArgs,
@@ -348,6 +418,14 @@ pub(crate) struct ValidateConfigArgs {
     /// The path to the configuration file.
     #[arg(value_name = "CONFIG")]
     pub(crate) configs: Vec<PathBuf>,
+
+    /// Treat unknown keys, deprecated values and risky regexes as errors.
+    #[arg(long)]
+    pub(crate) strict: bool,
+
+    /// Clone or reuse the remote repos, and check that every hook exists and is valid.
+    #[arg(long)]
+    pub(crate) check_remote: bool,
 }
 
 #[derive(Debug, Args)]
@@ -359,14 +437,25 @@ pub(crate) struct ValidateManifestArgs {
 
 #[derive(Debug, Args)]
 pub(crate) struct SampleConfigArgs {
-    /// Write the sample config to a file (`.pre-commit-config.yaml` by default).
-    #[arg(
-        short,
-        long,
-        num_args = 0..=1,
-        default_missing_value = CONFIG_FILE,
-    )]
-    pub(crate) file: Option<PathBuf>,
+    /// Write the sample config to a file (`.pre-commit-config.yaml` or `prek.toml` by default).
+    #[arg(short, long, num_args = 0..=1)]
+    #[allow(clippy::option_option)]
+    pub(crate) file: Option<Option<PathBuf>>,
+
+    /// The format of the sample config, detected from the file extension by default.
+    #[arg(long, value_enum)]
+    pub(crate) format: Option<ConfigFormat>,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct ConvertConfigArgs {
+    /// The config file to convert, YAML files are converted to TOML and vice versa.
+    #[arg(value_name = "CONFIG")]
+    pub(crate) input: PathBuf,
+
+    /// Write the converted config to a file instead of stdout.
+    #[arg(short, long)]
+    pub(crate) output: Option<PathBuf>,
 }
 
 #[derive(Debug, Args)]
@@ -417,6 +506,76 @@ pub struct SelfUpdateArgs {
     pub token: Option<String>,
 }
 
+#[derive(Debug, Args)]
+pub(crate) struct BundleNamespace {
+    #[command(subcommand)]
+    pub(crate) command: BundleCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum BundleCommand {
+    /// Export everything the config needs into a bundle.
+    ///
+    /// The bundle contains the cloned repos, the hook environments and the toolchains
+    /// they use, for machines without network access.
+    Export(BundleExportArgs),
+    /// Import a bundle into the store, relocating the hook environments.
+    Import(BundleImportArgs),
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct BundleExportArgs {
+    /// The bundle file to write, a `.tar.zst` archive.
+    pub(crate) file: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct BundleImportArgs {
+    /// The bundle file to import.
+    pub(crate) file: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct CacheNamespace {
+    #[command(subcommand)]
+    pub(crate) command: CacheCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum CacheCommand {
+    /// Show the cache directory.
+    Dir,
+    /// Show the disk usage of the cache, by bucket.
+    Size,
+    /// List the installed hook environments.
+    List,
+    /// Remove cloned repos, hook environments or toolchains from the cache.
+    Remove(CacheRemoveArgs),
+    /// Import the repos cloned by pre-commit into the store.
+    ///
+    /// The repos are read from the `db.db` database of the pre-commit cache,
+    /// so they don't need to be cloned again.
+    ImportPreCommit(CacheImportPreCommitArgs),
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct CacheRemoveArgs {
+    /// The entries to remove.
+    ///
+    /// An entry is a repo URL, the name of a hook environment as shown by `prek cache list`,
+    /// or a toolchain like `node` or `node/22.11.0`.
+    #[arg(required = true)]
+    pub(crate) entries: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct CacheImportPreCommitArgs {
+    /// The pre-commit cache directory.
+    ///
+    /// Defaults to `$PRE_COMMIT_HOME`, or `pre-commit` in `$XDG_CACHE_HOME` or `~/.cache`.
+    pub(crate) dir: Option<PathBuf>,
+}
+
 #[derive(Debug, Args)]
 pub(crate) struct GenerateShellCompletionArgs {
     /// The shell to generate the completion script for
@@ -424,6 +583,22 @@ pub(crate) struct GenerateShellCompletionArgs {
     pub shell: clap_complete::Shell,
 }
 
+#[derive(Debug, Copy, Clone, Default, clap::ValueEnum)]
+pub(crate) enum SchemaKind {
+    /// `.pre-commit-config.yaml` or `prek.toml` config files.
+    #[default]
+    Config,
+    /// `.pre-commit-hooks.yaml` manifest files.
+    Manifest,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct GenerateJsonSchemaArgs {
+    /// The kind of file to generate the schema for.
+    #[arg(value_enum, default_value_t)]
+    pub(crate) kind: SchemaKind,
+}
+
 #[derive(Debug, Args)]
 pub(crate) struct InitTemplateDirArgs {
     /// The directory in which to write the hook script.
@@ -59,6 +59,13 @@ impl ProgressReporter {
         id
     }
 
+    fn on_wait(&self, id: usize, msg: impl Into<Cow<'static, str>>) {
+        let state = self.state.lock().unwrap();
+        if let Some(progress) = state.bars.get(&id) {
+            progress.set_message(msg);
+        }
+    }
+
     fn on_progress(&self, id: usize) {
         let progress = {
             let mut state = self.state.lock().unwrap();
@@ -102,6 +109,17 @@ impl workspace::HookInitReporter for HookInitReporter {
             .on_start(format!("{} {}", "Cloning".bold().cyan(), repo.dimmed()))
     }
 
+    fn on_clone_wait(&self, id: usize, repo: &str) {
+        self.reporter.on_wait(
+            id,
+            format!(
+                "{} {}",
+                "Waiting".bold().yellow(),
+                format!("for another process cloning {repo}").dimmed()
+            ),
+        );
+    }
+
     fn on_clone_complete(&self, id: usize) {
         self.reporter.on_progress(id);
     }
@@ -141,6 +159,22 @@ impl HookInstallReporter {
         ))
     }
 
+    pub fn on_install_wait(&self, id: usize, hook: &Hook) {
+        self.reporter.on_wait(
+            id,
+            format!(
+                "{} {}",
+                "Waiting".bold().yellow(),
+                format!(
+                    "for another process installing the {} environment of {}",
+                    hook.language.as_str(),
+                    hook.id
+                )
+                .dimmed()
+            ),
+        );
+    }
+
     pub fn on_install_complete(&self, id: usize) {
         self.reporter.on_progress(id);
     }
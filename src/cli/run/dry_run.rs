@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use crate::builtin;
+use crate::cli::ExitStatus;
+use crate::cli::run::FileFilter;
+use crate::cli::run::run::shuffle;
+use crate::config::{Language, Stage};
+use crate::fs::Simplified;
+use crate::hook::Hook;
+use crate::printer::Printer;
+use crate::run::batches;
+
+/// Explain what `prek run` would do for each hook, without installing or running anything.
+///
+/// The hooks are resolved already, so remote repos have been cloned to read their manifests.
+///
+/// The commands shown are the entry, args and files of each batch, with the script of `script`
+/// hooks resolved against the repo. Hooks of other languages may not run the command as is,
+/// see [`runs_in`].
+pub(crate) fn explain(
+    hooks: &[Hook],
+    skips: &HashSet<usize>,
+    hook_stage: Stage,
+    filter: &FileFilter<'_>,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    for hook in hooks {
+        writeln!(printer.stdout(), "{}", hook.name.bold())?;
+        writeln!(
+            printer.stdout(),
+            "{}",
+            format!("- hook id: {}", hook.id).dimmed()
+        )?;
+
+        let mut filenames = filter.for_hook(hook)?;
+
+        let reason = if skips.contains(&hook.idx) {
+            Some(Cow::Borrowed("hook is skipped by `--skip` or `SKIP`"))
+        } else if !hook.stages.contains(&hook_stage) {
+            Some(Cow::Owned(format!(
+                "hook does not run at stage `{hook_stage}`"
+            )))
+        } else if !Language::supported(hook.language) {
+            Some(Cow::Owned(format!(
+                "language `{}` is not implemented yet",
+                hook.language
+            )))
+        } else if filenames.is_empty() && !hook.always_run {
+            Some(Cow::Borrowed("no files to check"))
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            writeln!(
+                printer.stdout(),
+                "{} {}",
+                "- skipped:".dimmed(),
+                reason.yellow()
+            )?;
+            continue;
+        }
+
+        writeln!(
+            printer.stdout(),
+            "{}",
+            format!("- files: {}", filenames.len()).dimmed()
+        )?;
+        for filename in &filenames {
+            writeln!(printer.stdout(), "    {filename}")?;
+        }
+
+        if builtin::check_fast_path(hook) {
+            writeln!(
+                printer.stdout(),
+                "{}",
+                "- command: builtin implementation".dimmed()
+            )?;
+            continue;
+        }
+
+        let filenames = if hook.pass_filenames {
+            shuffle(&mut filenames);
+            filenames
+        } else {
+            vec![]
+        };
+
+        let mut entry = hook.entry.parsed()?;
+        if hook.language == Language::Script {
+            let script = hook
+                .repo_path()
+                .unwrap_or(hook.project_dir())
+                .join(&entry[0]);
+            entry[0] = script.user_display().to_string();
+        }
+        let (concurrency, batches) = batches(hook, &filenames);
+        let parallel = if concurrency == 1 {
+            "run serially".to_string()
+        } else {
+            format!("up to {concurrency} at a time")
+        };
+        writeln!(
+            printer.stdout(),
+            "{}",
+            format!("- commands: {} ({parallel})", batches.len()).dimmed()
+        )?;
+        for batch in batches {
+            let command = entry
+                .iter()
+                .chain(&hook.args)
+                .chain(batch.iter().copied())
+                .map(|arg| shlex::try_quote(arg).unwrap_or(Cow::Borrowed(arg)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(printer.stdout(), "    {command}")?;
+        }
+        if let Some(runs_in) = runs_in(hook.language) {
+            writeln!(
+                printer.stdout(),
+                "{}",
+                format!("- runs in: {runs_in}").dimmed()
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Where the commands of hooks run, for languages that don't run them as shown.
+fn runs_in(language: Language) -> Option<&'static str> {
+    match language {
+        Language::Docker => Some(
+            "a container of the image built from the hook repo, with the first word of the entry as entrypoint",
+        ),
+        Language::DockerImage => Some("a container, the entry starts with the image to run"),
+        Language::Python | Language::Node | Language::Golang => {
+            Some("the hook environment, with its executables first in `PATH`")
+        }
+        Language::Fail => Some("nothing, the hook fails with the entry as message"),
+        _ => None,
+    }
+}
@@ -1,8 +1,6 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use fancy_regex as regex;
-use fancy_regex::Regex;
 use itertools::{Either, Itertools};
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use rustc_hash::FxHashSet;
@@ -10,48 +8,101 @@ use tracing::{debug, error};
 
 use constants::env_vars::EnvVars;
 
-use crate::config::Stage;
+use crate::config::{FileMatcher, FilePattern, PatternError, Stage};
+use crate::diagnostic::{LocatedError, Location};
 use crate::fs::normalize_path;
-use crate::hook::Hook;
+use crate::hook::{self, Hook};
 use crate::identify::tags_from_path;
+use crate::workspace::Project;
 use crate::{git, warn_user};
 
 /// Filter filenames by include/exclude patterns.
 pub(crate) struct FilenameFilter {
-    include: Option<Regex>,
-    exclude: Option<Regex>,
+    include: Option<FileMatcher>,
+    exclude: Option<FileMatcher>,
 }
 
 impl FilenameFilter {
     pub(crate) fn new(
-        include: Option<&str>,
-        exclude: Option<&str>,
-    ) -> Result<Self, Box<regex::Error>> {
-        let include = include.map(Regex::new).transpose()?;
-        let exclude = exclude.map(Regex::new).transpose()?;
+        include: Option<&FilePattern>,
+        exclude: Option<&FilePattern>,
+    ) -> Result<Self, PatternError> {
+        let include = include.map(FilePattern::compile).transpose()?;
+        let exclude = exclude.map(FilePattern::compile).transpose()?;
         Ok(Self { include, exclude })
     }
 
     pub(crate) fn filter(&self, filename: impl AsRef<str>) -> bool {
         let filename = filename.as_ref();
-        if let Some(re) = &self.include {
-            if !re.is_match(filename).unwrap_or(false) {
+        if let Some(include) = &self.include {
+            if !include.is_match(filename) {
                 return false;
             }
         }
-        if let Some(re) = &self.exclude {
-            if re.is_match(filename).unwrap_or(false) {
+        if let Some(exclude) = &self.exclude {
+            if exclude.is_match(filename) {
                 return false;
             }
         }
         true
     }
 
-    pub(crate) fn for_hook(hook: &Hook) -> Result<Self, Box<regex::Error>> {
-        Self::new(hook.files.as_deref(), hook.exclude.as_deref())
+    pub(crate) fn for_hook(hook: &Hook) -> Result<Self, hook::Error> {
+        let compile = |pattern: Option<&FilePattern>, key| {
+            let location = Location::HookKey {
+                hook: hook.id.clone(),
+                key,
+            };
+            compile(pattern, key, hook.config_path(), location).map_err(|error| {
+                hook::Error::InvalidHook {
+                    hook: hook.id.clone(),
+                    error,
+                }
+            })
+        };
+        Ok(Self {
+            include: compile(hook.files.as_ref(), "files")?,
+            exclude: compile(hook.exclude.as_ref(), "exclude")?,
+        })
+    }
+
+    fn for_project(project: &Project) -> Result<Self> {
+        let config = project.config();
+        let path = project.config_file();
+        Ok(Self {
+            include: compile(config.files.as_ref(), "files", path, Location::Key("files"))?,
+            exclude: compile(
+                config.exclude.as_ref(),
+                "exclude",
+                path,
+                Location::Key("exclude"),
+            )?,
+        })
     }
 }
 
+/// Compile a `files` or `exclude` pattern, the error points to where it is set in the config.
+fn compile(
+    pattern: Option<&FilePattern>,
+    key: &str,
+    config: &Path,
+    location: Location,
+) -> Result<Option<FileMatcher>> {
+    pattern
+        .map(|pattern| {
+            pattern.compile().map_err(|err| {
+                LocatedError::new(
+                    config,
+                    location,
+                    err.to_string(),
+                    format!("`{key}` is not a valid pattern: {err}"),
+                )
+                .into()
+            })
+        })
+        .transpose()
+}
+
 /// Filter files by tags.
 struct FileTagFilter<'a> {
     all: &'a [String],
@@ -92,22 +143,42 @@ impl<'a> FileTagFilter<'a> {
 
 pub(crate) struct FileFilter<'a> {
     filenames: Vec<&'a String>,
+    // The directory that the filenames are relative to.
+    work_dir: PathBuf,
 }
 
 impl<'a> FileFilter<'a> {
     pub(crate) fn new(
         filenames: &'a [String],
-        include: Option<&str>,
-        exclude: Option<&str>,
-    ) -> Result<Self, Box<regex::Error>> {
+        include: Option<&FilePattern>,
+        exclude: Option<&FilePattern>,
+    ) -> Result<Self, PatternError> {
         let filter = FilenameFilter::new(include, exclude)?;
+        Ok(Self::filtered(filenames, &filter))
+    }
 
+    /// Filter filenames by the top-level `files` and `exclude` patterns of the project.
+    pub(crate) fn for_project(filenames: &'a [String], project: &Project) -> Result<Self> {
+        let filter = FilenameFilter::for_project(project)?;
+        Ok(Self::filtered(filenames, &filter).with_work_dir(project.relative_path()))
+    }
+
+    fn filtered(filenames: &'a [String], filter: &FilenameFilter) -> Self {
         let filenames = filenames
             .into_par_iter()
             .filter(|filename| filter.filter(filename))
             .collect::<Vec<_>>();
 
-        Ok(Self { filenames })
+        Self {
+            filenames,
+            work_dir: PathBuf::new(),
+        }
+    }
+
+    /// Set the directory that the filenames are relative to, default to the current directory.
+    pub(crate) fn with_work_dir(mut self, work_dir: &Path) -> Self {
+        self.work_dir = work_dir.to_path_buf();
+        self
     }
 
     pub(crate) fn len(&self) -> usize {
@@ -126,8 +197,8 @@ impl<'a> FileFilter<'a> {
             .filenames
             .par_iter()
             .filter(|filename| {
-                let path = Path::new(filename);
-                match tags_from_path(path) {
+                let path = self.work_dir.join(filename);
+                match tags_from_path(&path) {
                     Ok(tags) => filter.filter(&tags),
                     Err(err) => {
                         error!(filename, error = %err, "Failed to get tags");
@@ -142,7 +213,7 @@ impl<'a> FileFilter<'a> {
     }
 
     /// Filter filenames by file patterns and tags for a specific hook.
-    pub(crate) fn for_hook(&self, hook: &Hook) -> Result<Vec<&String>, Box<regex::Error>> {
+    pub(crate) fn for_hook(&self, hook: &Hook) -> Result<Vec<&String>, hook::Error> {
         let filter = FilenameFilter::for_hook(hook)?;
         let filenames = self
             .filenames
@@ -152,8 +223,8 @@ impl<'a> FileFilter<'a> {
         let filter = FileTagFilter::for_hook(hook);
         let filenames: Vec<_> = filenames
             .filter(|filename| {
-                let path = Path::new(filename);
-                match tags_from_path(path) {
+                let path = self.work_dir.join(filename);
+                match tags_from_path(&path) {
                     Ok(tags) => filter.filter(&tags),
                     Err(err) => {
                         error!(filename, error = %err, "Failed to get tags");
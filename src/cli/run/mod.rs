@@ -1,7 +1,10 @@
 pub(crate) use filter::{CollectOptions, FileFilter, collect_files};
 pub(crate) use run::{install_hooks, run};
+pub(crate) use selector::HookSelector;
 
+mod dry_run;
 mod filter;
 mod keeper;
 #[allow(clippy::module_inception)]
 mod run;
+mod selector;
@@ -4,7 +4,7 @@ use std::fmt::Write as _;
 use std::hash::Hash;
 use std::io::Write;
 use std::ops::Deref;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -24,16 +24,21 @@ use unicode_width::UnicodeWidthStr;
 use constants::env_vars::EnvVars;
 
 use crate::cli::reporter::{HookInitReporter, HookInstallReporter};
+use crate::cli::run::dry_run;
 use crate::cli::run::keeper::WorkTreeKeeper;
+use crate::cli::run::selector::HookSelector;
 use crate::cli::run::{CollectOptions, FileFilter, collect_files};
 use crate::cli::{ExitStatus, RunExtraArgs};
 use crate::config::{Language, Stage};
 use crate::fs::Simplified;
 use crate::git;
-use crate::hook::{Hook, InstalledHook};
+use crate::hook::{Hook, InstallInfo, InstalledHook};
+use crate::languages::EntryNotFound;
+use crate::offline;
 use crate::printer::{Printer, Stdout};
 use crate::store::Store;
-use crate::workspace::Project;
+use crate::warn_user;
+use crate::workspace::{Project, Workspace};
 
 enum HookToRun {
     Skipped(Arc<Hook>),
@@ -54,7 +59,7 @@ impl Deref for HookToRun {
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub(crate) async fn run(
     config: Option<PathBuf>,
-    hook_id: Option<String>,
+    selector: HookSelector,
     hook_stage: Stage,
     from_ref: Option<String>,
     to_ref: Option<String>,
@@ -63,6 +68,8 @@ pub(crate) async fn run(
     directories: Vec<String>,
     last_commit: bool,
     show_diff_on_failure: bool,
+    dry_run: bool,
+    cd: Option<PathBuf>,
     extra_args: RunExtraArgs,
     verbose: bool,
     printer: Printer,
@@ -92,84 +99,180 @@ pub(crate) async fn run(
         return Ok(ExitStatus::Failure);
     }
 
-    let config_file = Project::find_config_file(config)?;
-    if should_stash && git::file_not_staged(&config_file).await? {
+    let store = Store::from_settings()?.init().await?;
+    let lock = store.lock_shared_async().await?;
+
+    let reporter = HookInitReporter::from(printer);
+    let mut workspace = Workspace::discover(config, cd.as_deref(), &store, Some(&reporter)).await?;
+    // Nothing is stashed in a dry run, so the configs don't need to be staged.
+    if should_stash && !dry_run {
+        for project in workspace.projects() {
+            if git::file_not_staged(project.config_file()).await? {
+                writeln!(
+                    printer.stderr(),
+                    indoc!(
+                        "Your pre-commit configuration file is not staged.
+                        Run `git add {}` to fix this."
+                    ),
+                    project.config_file().user_display()
+                )?;
+                return Ok(ExitStatus::Failure);
+            }
+        }
+    }
+
+    let mut project_hooks = Vec::with_capacity(workspace.projects().len());
+    let mut missing_group_hook = None;
+    for project in workspace.projects_mut() {
+        let reporter = HookInitReporter::from(printer);
+        let hooks = project.init_hooks(&store, Some(&reporter)).await?;
+
+        // Check the groups against all the hooks of the project, so only ids that are not
+        // defined at all are reported, not hooks filtered out by stage, language or repo.
+        if missing_group_hook.is_none() {
+            missing_group_hook = selector
+                .group_hooks(project.config())
+                .find(|(_, id)| !hooks.iter().any(|h| h.id == *id || h.alias == *id))
+                .map(|(group, id)| (group.to_string(), id.to_string()));
+        }
+        let hooks: Vec<_> = hooks
+            .into_iter()
+            .filter(|h| selector.matches(h, project.config()))
+            .collect();
+        project_hooks.push(hooks);
+    }
+
+    // Groups may be defined in the base configs, check them after the configs are resolved.
+    if let Some(group) = selector.undefined_group(
+        &workspace
+            .projects()
+            .iter()
+            .map(Project::config)
+            .collect::<Vec<_>>(),
+    ) {
         writeln!(
             printer.stderr(),
-            indoc!(
-                "Your pre-commit configuration file is not staged.
-                Run `git add {}` to fix this."
-            ),
-            &config_file.user_display()
+            "No group named `{}` found in the config",
+            group.cyan()
         )?;
         return Ok(ExitStatus::Failure);
     }
 
-    let mut project = Project::new(config_file)?;
-    let store = Store::from_settings()?.init()?;
-
-    let reporter = HookInitReporter::from(printer);
-
-    let lock = store.lock_async().await?;
-    let hooks = project.init_hooks(&store, Some(&reporter)).await?;
-
-    let hooks: Vec<_> = hooks
-        .into_iter()
-        .filter(|h| {
-            hook_id
-                .as_deref()
-                .is_none_or(|hook_id| h.id == hook_id || h.alias == hook_id)
-        })
-        .filter(|h| h.stages.contains(&hook_stage))
-        .collect();
-
-    if hooks.is_empty() && hook_id.is_some() {
+    if let Some(hook_id) = selector.hook_ids().iter().find(|&id| {
+        !project_hooks
+            .iter()
+            .flatten()
+            .any(|h| (h.id == *id || h.alias == *id) && h.stages.contains(&hook_stage))
+    }) {
         writeln!(
             printer.stderr(),
             "No hook found for id `{}` and stage `{}`",
-            hook_id.unwrap().cyan(),
+            hook_id.cyan(),
             hook_stage.cyan()
         )?;
         return Ok(ExitStatus::Failure);
     }
 
-    let skips = get_skips();
-    let skips = hooks
-        .iter()
-        .filter(|h| skips.contains(&h.id) || skips.contains(&h.alias))
-        .map(|h| h.idx)
-        .collect::<HashSet<_>>();
-    let to_run = hooks
-        .iter()
-        .filter(|h| !skips.contains(&h.idx))
-        .cloned()
-        .collect::<Vec<_>>();
+    if let Some((group, hook_id)) = missing_group_hook {
+        writeln!(
+            printer.stderr(),
+            "No hook found for id `{}` of group `{}`",
+            hook_id.cyan(),
+            group.cyan()
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
 
-    debug!(
-        "Hooks going to run: {:?}",
-        to_run.iter().map(|h| &h.id).collect::<Vec<_>>()
-    );
-    let reporter = HookInstallReporter::from(printer);
-    let mut installed_hooks = install_hooks(to_run, &store, &reporter).await?;
+    let collect_options = CollectOptions {
+        hook_stage,
+        from_ref: from_ref.clone(),
+        to_ref: to_ref.clone(),
+        all_files,
+        files,
+        directories,
+        commit_msg_filename: extra_args.commit_msg_filename.clone(),
+    };
 
-    // Release the store lock.
-    drop(lock);
+    if dry_run {
+        drop(lock);
 
-    let hooks = hooks
-        .into_iter()
-        .map(|h| {
-            if skips.contains(&h.idx) {
-                HookToRun::Skipped(Arc::new(h))
-            } else {
-                // Find and remove the matching resolved hook
-                let idx = installed_hooks
-                    .iter()
-                    .position(|r| r.idx == h.idx)
-                    .expect("Resolved hook must exist");
-                HookToRun::ToRun(Arc::new(installed_hooks.swap_remove(idx)))
+        let filenames = collect_files(collect_options).await?;
+        let project_files = workspace.route_files(&filenames);
+
+        let multiple = workspace.projects().len() > 1;
+        for ((project, hooks), filenames) in workspace
+            .projects()
+            .iter()
+            .zip(&project_hooks)
+            .zip(&project_files)
+        {
+            if multiple {
+                write_project_header(project, printer)?;
             }
-        })
-        .collect::<Vec<_>>();
+            let skips = hooks
+                .iter()
+                .filter(|h| selector.skipped(h))
+                .map(|h| h.idx)
+                .collect::<HashSet<_>>();
+            let filter = FileFilter::for_project(filenames, project)?;
+            dry_run::explain(hooks, &skips, hook_stage, &filter, printer)?;
+        }
+
+        return Ok(ExitStatus::Success);
+    }
+
+    let mut projects_to_run = Vec::with_capacity(project_hooks.len());
+    for (project, hooks) in workspace.projects().iter().zip(project_hooks) {
+        let hooks: Vec<_> = hooks
+            .into_iter()
+            .filter(|h| h.stages.contains(&hook_stage))
+            .map(|mut h| {
+                // Stream the hook output live in verbose mode.
+                h.stream = printer != Printer::Quiet && (verbose || h.stream);
+                h
+            })
+            .collect();
+
+        let skips = hooks
+            .iter()
+            .filter(|h| selector.skipped(h))
+            .map(|h| h.idx)
+            .collect::<HashSet<_>>();
+        let to_run = hooks
+            .iter()
+            .filter(|h| !skips.contains(&h.idx))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        debug!(
+            "Hooks going to run in `{}`: {:?}",
+            project.relative_path().display(),
+            to_run.iter().map(|h| &h.id).collect::<Vec<_>>()
+        );
+        let reporter = HookInstallReporter::from(printer);
+        let mut installed_hooks = install_hooks(to_run, &store, &reporter).await?;
+
+        let hooks = hooks
+            .into_iter()
+            .map(|h| {
+                if skips.contains(&h.idx) {
+                    HookToRun::Skipped(Arc::new(h))
+                } else {
+                    // Find and remove the matching resolved hook
+                    let idx = installed_hooks
+                        .iter()
+                        .position(|r| r.idx == h.idx)
+                        .expect("Resolved hook must exist");
+                    HookToRun::ToRun(Arc::new(installed_hooks.swap_remove(idx)))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        projects_to_run.push((project, hooks));
+    }
+
+    // Release the store lock.
+    drop(lock);
 
     // Clear any unstaged changes from the git working directory.
     let mut _guard = None;
@@ -179,29 +282,22 @@ pub(crate) async fn run(
 
     set_env_vars(from_ref.as_ref(), to_ref.as_ref(), &extra_args);
 
-    let filenames = collect_files(CollectOptions {
-        hook_stage,
-        from_ref,
-        to_ref,
-        all_files,
-        files,
-        directories,
-        commit_msg_filename: extra_args.commit_msg_filename.clone(),
-    })
-    .await?;
+    let filenames = collect_files(collect_options).await?;
+    let project_files = workspace.route_files(&filenames);
 
-    let filter = FileFilter::new(
-        &filenames,
-        project.config().files.as_deref(),
-        project.config().exclude.as_deref(),
-    )?;
-    trace!("Files after filtered: {}", filter.len());
+    let projects_to_run = projects_to_run
+        .into_iter()
+        .zip(&project_files)
+        .map(|((project, hooks), filenames)| ProjectHooks {
+            project,
+            hooks,
+            filenames,
+        })
+        .collect::<Vec<_>>();
 
     run_hooks(
-        &hooks,
-        &filter,
+        &projects_to_run,
         &store,
-        project.config().fail_fast.unwrap_or(false),
         show_diff_on_failure,
         verbose,
         printer,
@@ -209,6 +305,29 @@ pub(crate) async fn run(
     .await
 }
 
+/// The hooks to run of a project, and the files routed to it.
+struct ProjectHooks<'a> {
+    project: &'a Project,
+    hooks: Vec<HookToRun>,
+    filenames: &'a [String],
+}
+
+fn write_project_header(project: &Project, printer: Printer) -> Result<()> {
+    let path = project.relative_path();
+    let path = if path.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        path
+    };
+    writeln!(
+        printer.stdout(),
+        "{}{}",
+        "Running hooks for ".bold(),
+        format!("`{}`:", path.display()).cyan().bold()
+    )?;
+    Ok(())
+}
+
 // `pre-commit` sets these environment variables for other git hooks.
 fn set_env_vars(from_ref: Option<&String>, to_ref: Option<&String>, args: &RunExtraArgs) {
     unsafe {
@@ -258,15 +377,28 @@ fn set_env_vars(from_ref: Option<&String>, to_ref: Option<&String>, args: &RunEx
     }
 }
 
-fn get_skips() -> Vec<String> {
-    match EnvVars::var_os(EnvVars::SKIP) {
-        Some(s) if !s.is_empty() => s
-            .to_string_lossy()
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect(),
-        _ => vec![],
+/// Whether an installed environment works for the hook.
+///
+/// Broken environments are marked so that no process uses them again, they are removed once
+/// no process uses the store.
+async fn check_env(hook: &Hook, info: &InstallInfo) -> bool {
+    match hook.language.check_health(hook, info).await {
+        Ok(()) => true,
+        Err(err) if err.is::<EntryNotFound>() => {
+            // The entry may be a path relative to the working directory of the hook.
+            debug!(%hook, %err, "Entry not found in environment");
+            true
+        }
+        Err(err) => {
+            warn_user!(
+                "The environment of hook `{hook}` at `{}` is broken, reinstalling: {err}",
+                info.env_path.user_display()
+            );
+            if let Err(err) = info.mark_broken() {
+                debug!(%err, "Failed to mark environment as broken");
+            }
+            false
+        }
     }
 }
 
@@ -308,8 +440,9 @@ pub async fn install_hooks(
                 let mut newly_installed = Vec::new();
 
                 for hook in hooks {
-                    // Find a matching installed hook environment.
-                    if let Some(info) = installed_hooks
+                    // Find a matching installed hook environment that still works.
+                    let mut found = None;
+                    let candidates = installed_hooks
                         .iter()
                         .chain(newly_installed.iter().filter_map(|h| {
                             if let InstalledHook::Installed { info, .. } = h {
@@ -318,32 +451,65 @@ pub async fn install_hooks(
                                 None
                             }
                         }))
-                        .find(|info| info.matches(&hook))
-                    {
+                        // Skip environments found broken for an earlier hook.
+                        .filter(|info| info.matches(&hook) && !info.is_broken());
+                    for info in candidates {
+                        if check_env(&hook, info).await {
+                            found = Some(info.clone());
+                            break;
+                        }
+                    }
+                    if let Some(info) = found {
                         debug!(
                             "Found installed environment for hook `{}` at `{}`",
                             &hook,
                             info.env_path.display()
                         );
+                        if let Err(err) = info.mark_used() {
+                            trace!(%err, "Failed to mark environment as used");
+                        }
                         hook_envs.push(InstalledHook::Installed {
                             hook: Arc::new(hook),
-                            info: Arc::new(info.clone()),
+                            info: Arc::new(info),
                         });
                         continue;
                     }
 
                     let hook = Arc::new(hook);
-                    debug!("No matching environment found for hook `{hook}`, installing...");
-
                     let progress = reporter.on_install_start(&hook);
 
+                    let _lock = store
+                        .lock_env(&hook, || reporter.on_install_wait(progress, &hook))
+                        .await?;
+                    // Another process may have installed a matching environment meanwhile.
+                    let mut found = None;
+                    for info in store.installed_hooks().filter(|info| info.matches(&hook)) {
+                        if check_env(&hook, &info).await {
+                            found = Some(info);
+                            break;
+                        }
+                    }
+                    if let Some(info) = found {
+                        debug!(
+                            "Found environment for hook `{hook}` installed by another process at `{}`",
+                            info.env_path.display()
+                        );
+                        newly_installed.push(InstalledHook::Installed {
+                            hook,
+                            info: Arc::new(info),
+                        });
+                        reporter.on_install_complete(progress);
+                        continue;
+                    }
+                    debug!("No matching environment found for hook `{hook}`, installing...");
+
                     let installed_hook = hook
                         .language
                         .install(hook.clone(), store)
                         .await
                         .context(format!("Failed to install hook `{hook}`"))?;
 
-                    installed_hook
+                    let installed_hook = installed_hook
                         .mark_as_installed(store)
                         .await
                         .context(format!("Failed to mark hook `{hook}` as installed"))?;
@@ -369,11 +535,20 @@ pub async fn install_hooks(
         }
     }
 
+    // In offline mode, collect everything that is missing instead of failing on the first one.
+    let mut missing = offline::Missing::default();
     while let Some(result) = group_futures.next().await {
-        new_installed.extend(result?);
+        match result {
+            Ok(hooks) => new_installed.extend(hooks),
+            Err(err) => missing.extend(err.downcast::<offline::Missing>()?),
+        }
     }
     reporter.on_complete();
 
+    if !missing.is_empty() {
+        return Err(missing.into());
+    }
+
     debug_assert_eq!(
         num_hooks,
         new_installed.len(),
@@ -503,34 +678,49 @@ impl StatusPrinter {
     }
 }
 
-/// Run all hooks.
+/// Run all hooks, project by project.
 async fn run_hooks(
-    hooks: &[HookToRun],
-    filter: &FileFilter<'_>,
+    projects: &[ProjectHooks<'_>],
     store: &Store,
-    fail_fast: bool,
     show_diff_on_failure: bool,
     verbose: bool,
     printer: Printer,
 ) -> Result<ExitStatus> {
-    let printer = StatusPrinter::for_hooks(hooks, printer);
+    let multiple = projects.len() > 1;
     let mut success = true;
 
     let mut diff = git::get_diff().await?;
-    // Hooks might modify the files, so they must be run sequentially.
-    for hook in hooks {
-        let (hook_success, new_diff) =
-            run_hook(hook, filter, store, diff, verbose, &printer).await?;
-
-        success &= hook_success;
-        diff = new_diff;
-        let fail_fast = fail_fast
-            || match hook {
-                HookToRun::Skipped(_) => false,
-                HookToRun::ToRun(hook) => hook.fail_fast,
-            };
-        if !success && fail_fast {
-            break;
+    'projects: for ProjectHooks {
+        project,
+        hooks,
+        filenames,
+    } in projects
+    {
+        if multiple {
+            write_project_header(project, printer)?;
+        }
+
+        let filter = FileFilter::for_project(filenames, project)?;
+        trace!("Files after filtered: {}", filter.len());
+
+        let project_fail_fast = project.config().fail_fast.unwrap_or(false);
+        let printer = StatusPrinter::for_hooks(hooks, printer);
+
+        // Hooks might modify the files, so they must be run sequentially.
+        for hook in hooks {
+            let (hook_success, new_diff) =
+                run_hook(hook, &filter, store, diff, verbose, &printer).await?;
+
+            success &= hook_success;
+            diff = new_diff;
+            let fail_fast = project_fail_fast
+                || match hook {
+                    HookToRun::Skipped(_) => false,
+                    HookToRun::ToRun(hook) => hook.fail_fast,
+                };
+            if !success && fail_fast {
+                break 'projects;
+            }
         }
     }
 
@@ -561,7 +751,7 @@ async fn run_hooks(
 
 /// Shuffle the files so that they more evenly fill out the xargs
 /// partitions, but do it deterministically in case a hook cares about ordering.
-fn shuffle<T>(filenames: &mut [T]) {
+pub(super) fn shuffle<T>(filenames: &mut [T]) {
     const SEED: u64 = 1_542_676_187;
     let mut rng = StdRng::seed_from_u64(SEED);
     filenames.shuffle(&mut rng);
@@ -603,8 +793,12 @@ async fn run_hook(
         return Ok((true, diff));
     }
 
-    printer.write_running(&hook.name)?;
-    std::io::stdout().flush()?;
+    // When the output is streamed, the status line is written after the hook finishes,
+    // so that it's not interleaved with the hook output.
+    if !hook.stream {
+        printer.write_running(&hook.name)?;
+        std::io::stdout().flush()?;
+    }
 
     let start = std::time::Instant::now();
 
@@ -626,6 +820,9 @@ async fn run_hook(
     let new_diff = git::get_diff().await?;
     let file_modified = diff != new_diff;
     let success = status == 0 && !file_modified;
+    if hook.stream {
+        printer.write_running(&hook.name)?;
+    }
     if success {
         printer.write_passed()?;
     } else {
@@ -671,7 +868,7 @@ async fn run_hook(
                     .await?;
                 file.write_all(stdout).await?;
                 file.sync_all().await?;
-            } else {
+            } else if !hook.stream {
                 writeln!(
                     printer.stdout(),
                     "{}",
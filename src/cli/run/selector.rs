@@ -0,0 +1,119 @@
+use constants::env_vars::EnvVars;
+
+use crate::cli::RunArgs;
+use crate::config::{Config, Language};
+use crate::hook::{Hook, Repo};
+
+/// Select the hooks to run from the command line arguments and the `SKIP` environment variable.
+#[derive(Debug, Default)]
+pub(crate) struct HookSelector {
+    hook_ids: Vec<String>,
+    tags: Vec<String>,
+    groups: Vec<String>,
+    skips: Vec<String>,
+    repos: Vec<String>,
+    languages: Vec<Language>,
+}
+
+impl HookSelector {
+    pub(crate) fn from_args(args: &RunArgs) -> Self {
+        let mut skips = args.skip.clone();
+        skips.extend(get_skips());
+
+        Self {
+            hook_ids: args.hook_ids.clone(),
+            tags: args.tag.clone(),
+            groups: args.group.clone(),
+            skips,
+            repos: args.repo.clone(),
+            languages: args.language.clone(),
+        }
+    }
+
+    pub(crate) fn hook_ids(&self) -> &[String] {
+        &self.hook_ids
+    }
+
+    /// Return the first selected group that is not defined in any of the configs.
+    pub(crate) fn undefined_group(&self, configs: &[&Config]) -> Option<&str> {
+        self.groups
+            .iter()
+            .find(|group| {
+                !configs.iter().any(|config| {
+                    config
+                        .groups
+                        .as_ref()
+                        .is_some_and(|g| g.contains_key(*group))
+                })
+            })
+            .map(String::as_str)
+    }
+
+    /// The hook ids of the selected groups, with their group, as defined in the project config.
+    pub(crate) fn group_hooks<'a>(
+        &'a self,
+        config: &'a Config,
+    ) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.groups
+            .iter()
+            .filter_map(|group| Some((group, config.groups.as_ref()?.get(group)?)))
+            .flat_map(|(group, ids)| ids.iter().map(|id| (group.as_str(), id.as_str())))
+    }
+
+    /// Whether the hook matches the hook ids, groups, tags, repos and languages selected.
+    ///
+    /// A hook is selected by any of its id, group or tag, and must also match the selected
+    /// repos and languages. Groups are looked up in the config of the hook's project.
+    pub(crate) fn matches(&self, hook: &Hook, config: &Config) -> bool {
+        let matches_id =
+            (self.hook_ids.is_empty() && self.groups.is_empty() && self.tags.is_empty())
+                || self
+                    .hook_ids
+                    .iter()
+                    .map(String::as_str)
+                    .chain(self.group_hooks(config).map(|(_, id)| id))
+                    .any(|id| id == hook.id || id == hook.alias)
+                || hook.tags.iter().any(|tag| self.tags.contains(tag));
+        let matches_repo = self.repos.is_empty()
+            || self
+                .repos
+                .iter()
+                .any(|repo| repo_matches(hook.repo(), repo));
+        let matches_language = self.languages.is_empty() || self.languages.contains(&hook.language);
+
+        matches_id && matches_repo && matches_language
+    }
+
+    /// Whether the hook is skipped by `--skip` or the `SKIP` environment variable.
+    pub(crate) fn skipped(&self, hook: &Hook) -> bool {
+        self.skips
+            .iter()
+            .any(|skip| *skip == hook.id || *skip == hook.alias)
+    }
+}
+
+/// Compare repo urls, ignoring a trailing slash or `.git` suffix.
+fn repo_matches(repo: &Repo, selected: &str) -> bool {
+    fn normalize(url: &str) -> &str {
+        let url = url.trim_end_matches('/');
+        url.strip_suffix(".git").unwrap_or(url)
+    }
+
+    match repo {
+        Repo::Remote { url, .. } => normalize(url.as_str()) == normalize(selected),
+        Repo::Local { .. } => selected == "local",
+        Repo::Meta { .. } => selected == "meta",
+    }
+}
+
+fn get_skips() -> Vec<String> {
+    match EnvVars::var_os(EnvVars::SKIP) {
+        Some(s) if !s.is_empty() => s
+            .to_string_lossy()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => vec![],
+    }
+}
@@ -5,6 +5,7 @@ use anyhow::Result;
 use owo_colors::OwoColorize;
 
 use crate::cli::ExitStatus;
+use crate::config::ConfigFormat;
 use crate::fs::Simplified;
 use crate::printer::Printer;
 
@@ -21,14 +22,40 @@ repos:
       - id: check-added-large-files
 ";
 
-#[allow(clippy::print_stdout)]
-pub(crate) fn sample_config(file: Option<PathBuf>, printer: Printer) -> Result<ExitStatus> {
+static SAMPLE_CONFIG_TOML: &str = r#"# See https://pre-commit.com for more information
+# See https://pre-commit.com/hooks.html for more hooks
+[[repos]]
+repo = "https://github.com/pre-commit/pre-commit-hooks"
+rev = "v5.0.0"
+hooks = [
+  { id = "trailing-whitespace" },
+  { id = "end-of-file-fixer" },
+  { id = "check-yaml" },
+  { id = "check-added-large-files" },
+]
+"#;
+
+#[allow(clippy::print_stdout, clippy::option_option)]
+pub(crate) fn sample_config(
+    file: Option<Option<PathBuf>>,
+    format: Option<ConfigFormat>,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let format = format
+        .or_else(|| file.as_ref()?.as_deref().map(ConfigFormat::from_path))
+        .unwrap_or_default();
+    let sample = match format {
+        ConfigFormat::Yaml => SAMPLE_CONFIG,
+        ConfigFormat::Toml => SAMPLE_CONFIG_TOML,
+    };
+
     if let Some(file) = file {
+        let file = file.unwrap_or_else(|| PathBuf::from(format.default_file()));
         fs_err::create_dir_all(file.parent().unwrap_or(Path::new(".")))?;
         if file.exists() {
             anyhow::bail!("File `{}` already exists", file.simplified_display().cyan());
         }
-        fs_err::write(&file, SAMPLE_CONFIG)?;
+        fs_err::write(&file, sample)?;
 
         writeln!(
             printer.stdout(),
@@ -39,6 +66,6 @@ pub(crate) fn sample_config(file: Option<PathBuf>, printer: Printer) -> Result<E
         return Ok(ExitStatus::Success);
     }
 
-    print!("{SAMPLE_CONFIG}");
+    print!("{sample}");
     Ok(ExitStatus::Success)
 }
@@ -29,6 +29,7 @@ use owo_colors::OwoColorize;
 use tracing::{debug, enabled};
 
 use crate::cli::ExitStatus;
+use crate::offline;
 use crate::printer::Printer;
 
 /// Attempt to update the prek binary.
@@ -37,6 +38,19 @@ pub(crate) async fn self_update(
     token: Option<String>,
     printer: Printer,
 ) -> Result<ExitStatus> {
+    if offline::is_enabled() {
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format_args!(
+                "{}{} Self-update needs network access, which is not allowed in offline mode.",
+                "error".red().bold(),
+                ":".bold()
+            )
+        )?;
+        return Ok(ExitStatus::Error);
+    }
+
     let mut updater = AxoUpdater::new_for("prek");
     if enabled!(tracing::Level::DEBUG) {
         unsafe { env::set_var("INSTALLER_PRINT_VERBOSE", "1") };
@@ -1,27 +1,78 @@
 use std::error::Error;
 use std::iter;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anstream::eprintln;
+use anyhow::Result;
 use owo_colors::OwoColorize;
 
 use crate::cli::ExitStatus;
-use crate::config::{read_config, read_manifest};
+use crate::cli::reporter::HookInitReporter;
+use crate::config::{read_config_with_warnings, read_manifest};
+use crate::diagnostic;
+use crate::printer::Printer;
+use crate::store::Store;
+use crate::warn_user;
+use crate::workspace::{self, Project};
 
-pub(crate) fn validate_configs(configs: Vec<PathBuf>) -> ExitStatus {
+pub(crate) async fn validate_configs(
+    configs: Vec<PathBuf>,
+    strict: bool,
+    check_remote: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
     let mut status = ExitStatus::Success;
 
+    let store = if check_remote {
+        Some(Store::from_settings()?.init().await?)
+    } else {
+        None
+    };
+    let _lock = match &store {
+        Some(store) => Some(store.lock_shared_async().await?),
+        None => None,
+    };
+
     for config in configs {
-        if let Err(err) = read_config(&config) {
-            eprintln!("{}: {}", "error".red().bold(), err);
-            for source in iter::successors(err.source(), |&err| err.source()) {
-                eprintln!("  {}: {}", "caused by".red().bold(), source);
+        match read_config_with_warnings(&config) {
+            Ok((_, warnings)) => {
+                for warning in warnings {
+                    if strict {
+                        eprintln!("{}: {}", "error".red().bold(), warning);
+                        status = ExitStatus::Failure;
+                    } else {
+                        warn_user!("{warning}");
+                    }
+                }
+
+                if let Some(store) = &store {
+                    if let Err(err) = check_hooks(&config, store, printer).await {
+                        report(&err);
+                        status = ExitStatus::Failure;
+                    }
+                }
+            }
+            Err(err) => {
+                report(&err);
+                status = ExitStatus::Failure;
             }
-            status = ExitStatus::Failure;
         }
     }
 
-    status
+    Ok(status)
+}
+
+/// Initialize the repos of the config, and build every hook to check it.
+async fn check_hooks(
+    config: &Path,
+    store: &Store,
+    printer: Printer,
+) -> Result<(), workspace::Error> {
+    let reporter = HookInitReporter::from(printer);
+    let mut project = Project::new(config.to_path_buf(), store, Some(&reporter)).await?;
+    let reporter = HookInitReporter::from(printer);
+    project.init_hooks(store, Some(&reporter)).await?;
+    Ok(())
 }
 
 pub(crate) fn validate_manifest(configs: Vec<PathBuf>) -> ExitStatus {
@@ -29,13 +80,21 @@ pub(crate) fn validate_manifest(configs: Vec<PathBuf>) -> ExitStatus {
 
     for config in configs {
         if let Err(err) = read_manifest(&config) {
-            eprintln!("{}: {}", "error".red().bold(), err);
-            for source in iter::successors(err.source(), |&err| err.source()) {
-                eprintln!("  {}: {}", "caused by".red().bold(), source);
-            }
+            report(&err);
             status = ExitStatus::Failure;
         }
     }
 
     status
 }
+
+/// Print the error with its causes, and the config snippet it points to.
+fn report(err: &(dyn Error + 'static)) {
+    eprintln!("{}: {}", "error".red().bold(), err);
+    for source in iter::successors(err.source(), |&err| err.source()) {
+        eprintln!("  {}: {}", "caused by".red().bold(), source);
+    }
+    if let Some(snippet) = diagnostic::render(err) {
+        eprintln!("\n{snippet}");
+    }
+}
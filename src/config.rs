@@ -1,22 +1,34 @@
-use std::collections::HashMap;
-use std::fmt::Display;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Write};
 use std::ops::RangeInclusive;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::LazyLock;
 
 use anyhow::Result;
 use fancy_regex as regex;
+use schemars::JsonSchema;
+use serde::de::{IgnoredAny, IntoDeserializer};
 use serde::{Deserialize, Deserializer, Serialize};
 use url::Url;
 
+use crate::diagnostic::{LocatedError, Location};
 use crate::fs::Simplified;
+use crate::version::{MinimumVersionError, check_minimum_versions, max_minimum_version};
+use crate::warn_user_once;
 
 pub const CONFIG_FILE: &str = ".pre-commit-config.yaml";
 pub const ALTER_CONFIG_FILE: &str = ".pre-commit-config.yml";
+pub const PREK_TOML: &str = "prek.toml";
+/// Config file names, in the order of precedence.
+pub const CONFIG_FILES: [&str; 3] = [PREK_TOML, CONFIG_FILE, ALTER_CONFIG_FILE];
 pub const MANIFEST_FILE: &str = ".pre-commit-hooks.yaml";
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
 #[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
 pub enum Language {
     Conda,
     Coursier,
@@ -73,7 +85,7 @@ impl Display for Language {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, Deserialize, clap::ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema, clap::ValueEnum)]
 #[serde(rename_all = "kebab-case")]
 pub enum HookType {
     CommitMsg,
@@ -128,7 +140,7 @@ impl Display for HookType {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, clap::ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, JsonSchema, clap::ValueEnum)]
 #[serde(rename_all = "kebab-case")]
 pub enum Stage {
     Manual,
@@ -138,11 +150,8 @@ pub enum Stage {
     PostMerge,
     PostRewrite,
     #[default]
-    #[serde(alias = "commit")]
     PreCommit,
-    #[serde(alias = "merge-commit")]
     PreMergeCommit,
-    #[serde(alias = "push")]
     PrePush,
     PreRebase,
     PrepareCommitMsg,
@@ -203,11 +212,77 @@ impl Stage {
     }
 }
 
-// TODO: warn unexpected keys
-// TODO: warn deprecated stage
-// TODO: warn sensible regex
-// TODO: check minimum_pre_commit_version
-#[derive(Debug, Clone, Deserialize)]
+/// Stage names deprecated by pre-commit, and their replacements.
+const LEGACY_STAGES: &[(&str, Stage)] = &[
+    ("commit", Stage::PreCommit),
+    ("merge-commit", Stage::PreMergeCommit),
+    ("push", Stage::PrePush),
+];
+
+/// A list of stages, remembering the deprecated stage names it was written with.
+#[derive(Debug, Clone)]
+pub struct Stages {
+    stages: Vec<Stage>,
+    deprecated: Vec<(&'static str, Stage)>,
+}
+
+impl From<Vec<Stage>> for Stages {
+    fn from(stages: Vec<Stage>) -> Self {
+        Self {
+            stages,
+            deprecated: Vec::new(),
+        }
+    }
+}
+
+impl Stages {
+    /// Describe the deprecated stage names, found in the given location.
+    fn describe_deprecated<'a>(&'a self, location: &'a str) -> impl Iterator<Item = String> + 'a {
+        self.deprecated.iter().map(move |(name, replacement)| {
+            format!("Stage `{name}` in {location} is deprecated, use `{replacement}` instead")
+        })
+    }
+}
+
+impl From<Stages> for Vec<Stage> {
+    fn from(stages: Stages) -> Self {
+        stages.stages
+    }
+}
+
+impl<'de> Deserialize<'de> for Stages {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StageName(Stage, Option<(&'static str, Stage)>);
+
+        impl<'de> Deserialize<'de> for StageName {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let name = String::deserialize(deserializer)?;
+                if let Some(&(legacy, stage)) =
+                    LEGACY_STAGES.iter().find(|(legacy, _)| *legacy == name)
+                {
+                    return Ok(StageName(stage, Some((legacy, stage))));
+                }
+                let stage = Stage::deserialize(name.into_deserializer())?;
+                Ok(StageName(stage, None))
+            }
+        }
+
+        let names = Vec::<StageName>::deserialize(deserializer)?;
+        Ok(Self {
+            stages: names.iter().map(|name| name.0).collect(),
+            deprecated: names.iter().filter_map(|name| name.1).collect(),
+        })
+    }
+}
+
+/// A `.pre-commit-config.yaml` or `prek.toml` config file.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Config {
     pub repos: Vec<Repo>,
@@ -218,17 +293,166 @@ pub struct Config {
     pub default_language_version: Option<HashMap<Language, String>>,
     /// A configuration-wide default for the stages property of hooks.
     /// Default to all stages.
-    pub default_stages: Option<Vec<Stage>>,
+    #[schemars(with = "Option<Vec<Stage>>")]
+    pub default_stages: Option<Stages>,
     /// Global file include pattern.
-    pub files: Option<String>,
+    pub files: Option<FilePattern>,
     /// Global file exclude pattern.
-    pub exclude: Option<String>,
+    pub exclude: Option<FilePattern>,
     /// Set to true to have pre-commit stop running hooks after the first failure.
     /// Default is false.
     pub fail_fast: Option<bool>,
+    /// The minimum version of pre-commit the config requires.
     pub minimum_pre_commit_version: Option<String>,
+    /// The minimum version of prek the config requires.
+    pub minimum_prek_version: Option<String>,
     /// Configuration for pre-commit.ci service.
+    #[schemars(with = "Option<HashMap<String, serde_json::Value>>")]
     pub ci: Option<HashMap<String, serde_yaml::Value>>,
+    /// Named groups of hook ids, which can be selected with `prek run --group <NAME>`.
+    pub groups: Option<HashMap<String, Vec<String>>>,
+    /// Base configs to inherit from, merged in order before this config.
+    ///
+    /// Either a path relative to the config file, or a file in a remote repo as
+    /// `<repo>@<rev>:<path>`.
+    #[schemars(with = "Option<Vec<String>>")]
+    pub extends: Option<Vec<ConfigBase>>,
+    /// Hook ids inherited from the base configs to disable.
+    pub disabled_hooks: Option<Vec<String>>,
+    #[serde(flatten)]
+    #[schemars(skip)]
+    pub unknown: UnknownKeys,
+}
+
+impl Config {
+    /// Merge the base config into this config.
+    ///
+    /// Repos of the base config come first, without the hooks that are disabled or redefined
+    /// by id in this config. A redefined hook replaces the base hook as a whole, its options
+    /// are not merged. Other settings of this config take precedence over the base, except
+    /// the minimum versions, where the higher one is kept.
+    pub fn inherit(&mut self, base: Config) {
+        let overridden: HashSet<&str> = self
+            .repos
+            .iter()
+            .flat_map(Repo::hook_ids)
+            .chain(self.disabled_hooks.iter().flatten().map(String::as_str))
+            .collect();
+
+        let mut repos: Vec<Repo> = base
+            .repos
+            .into_iter()
+            .filter_map(|mut repo| {
+                repo.retain_hooks(|id| !overridden.contains(id));
+                (!repo.hook_ids().is_empty()).then_some(repo)
+            })
+            .collect();
+        repos.append(&mut self.repos);
+        self.repos = repos;
+
+        macro_rules! inherit_if_none {
+            ($($field:ident),* $(,)?) => {
+                $(
+                if self.$field.is_none() {
+                    self.$field = base.$field;
+                }
+                )*
+            };
+        }
+
+        inherit_if_none!(
+            default_install_hook_types,
+            default_stages,
+            files,
+            exclude,
+            fail_fast,
+            ci,
+        );
+
+        // Both configs must be supported, so keep the higher of the minimum versions.
+        macro_rules! inherit_max_version {
+            ($($field:ident),* $(,)?) => {
+                $(
+                self.$field = match (self.$field.take(), base.$field) {
+                    (Some(version), Some(other)) => Some(max_minimum_version(version, other)),
+                    (version, other) => version.or(other),
+                };
+                )*
+            };
+        }
+
+        inherit_max_version!(minimum_pre_commit_version, minimum_prek_version);
+
+        // Merge the maps, entries of this config take precedence.
+        if let Some(mut base_versions) = base.default_language_version {
+            base_versions.extend(self.default_language_version.take().unwrap_or_default());
+            self.default_language_version = Some(base_versions);
+        }
+        if let Some(mut base_groups) = base.groups {
+            base_groups.extend(self.groups.take().unwrap_or_default());
+            self.groups = Some(base_groups);
+        }
+    }
+}
+
+/// A base config to extend, either a path relative to the config file,
+/// or a file in a remote repo as `<repo>@<rev>:<path>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigBase {
+    Local(PathBuf),
+    Remote {
+        repo: Url,
+        rev: String,
+        path: String,
+    },
+}
+
+impl FromStr for ConfigBase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.contains("://") {
+            return Ok(ConfigBase::Local(PathBuf::from(s)));
+        }
+
+        let Some((repo, rest)) = s.rsplit_once('@') else {
+            return Err(format!(
+                "Missing `@<rev>:<path>` in remote base config `{s}`"
+            ));
+        };
+        let Some((rev, path)) = rest.split_once(':') else {
+            return Err(format!("Missing `:<path>` in remote base config `{s}`"));
+        };
+        if rev.is_empty() || path.is_empty() {
+            return Err(format!("Invalid remote base config `{s}`"));
+        }
+        let repo = Url::parse(repo).map_err(|e| format!("Invalid repo URL `{repo}`: {e}"))?;
+
+        Ok(ConfigBase::Remote {
+            repo,
+            rev: rev.to_string(),
+            path: path.to_string(),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ConfigBase {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        ConfigBase::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Display for ConfigBase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigBase::Local(path) => write!(f, "{}", path.display()),
+            ConfigBase::Remote { repo, rev, path } => write!(f, "{repo}@{rev}:{path}"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -277,16 +501,109 @@ impl Display for RepoLocation {
     }
 }
 
+/// A `files` or `exclude` pattern.
+///
+/// A string is a Python-style regex as in pre-commit, while `{glob: <GLOB>}` or
+/// `{glob: [<GLOB>, ...]}` matches files with globs instead.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum FilePattern {
+    /// A regex, searched in the file path.
+    Regex(String),
+    /// Globs matched against the whole file path, any of them can match.
+    /// `*` does not match `/`, while `**` matches any number of directories.
+    Glob {
+        #[serde(deserialize_with = "one_or_many")]
+        #[schemars(with = "OneOrMany")]
+        glob: Vec<String>,
+    },
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(glob) => vec![glob],
+        OneOrMany::Many(globs) => globs,
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PatternError {
+    #[error(transparent)]
+    Regex(#[from] Box<regex::Error>),
+    #[error(transparent)]
+    Glob(#[from] globset::Error),
+}
+
+/// A compiled `files` or `exclude` pattern.
+pub enum FileMatcher {
+    Regex(regex::Regex),
+    Glob(globset::GlobSet),
+}
+
+impl FilePattern {
+    /// A regex pattern.
+    pub fn regex(pattern: impl Into<String>) -> Self {
+        Self::Regex(pattern.into())
+    }
+
+    pub fn compile(&self) -> Result<FileMatcher, PatternError> {
+        match self {
+            Self::Regex(pattern) => Ok(FileMatcher::Regex(
+                regex::Regex::new(pattern).map_err(Box::new)?,
+            )),
+            Self::Glob { glob } => {
+                let mut builder = globset::GlobSetBuilder::new();
+                for glob in glob {
+                    builder.add(
+                        globset::GlobBuilder::new(glob)
+                            .literal_separator(true)
+                            .build()?,
+                    );
+                }
+                Ok(FileMatcher::Glob(builder.build()?))
+            }
+        }
+    }
+}
+
+impl Display for FilePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Regex(pattern) => f.write_str(pattern),
+            Self::Glob { glob } => write!(f, "glob: {}", glob.join(", ")),
+        }
+    }
+}
+
+impl FileMatcher {
+    pub fn is_match(&self, path: &str) -> bool {
+        match self {
+            Self::Regex(regex) => regex.is_match(path).unwrap_or(false),
+            Self::Glob(globs) => globs.is_match(path),
+        }
+    }
+}
+
 /// Common hook options.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
 pub struct HookOptions {
     /// Not documented in the official docs.
     pub alias: Option<String>,
     /// The pattern of files to run on.
-    pub files: Option<String>,
+    pub files: Option<FilePattern>,
     /// Exclude files that were matched by `files`.
     /// Default is `$^`, which matches nothing.
-    pub exclude: Option<String>,
+    pub exclude: Option<FilePattern>,
     /// List of file types to run on (AND).
     /// Default is `[file]`, which matches all files.
     pub types: Option<Vec<String>>,
@@ -312,6 +629,8 @@ pub struct HookOptions {
     /// A description of the hook. For metadata only.
     pub description: Option<String>,
     /// Run the hook on a specific version of the language.
+    /// Besides the values pre-commit accepts, prek also accepts version requirements
+    /// like `>=3.10`.
     /// Default is `default`.
     /// See <https://pre-commit.com/#overriding-language-version>.
     pub language_version: Option<String>,
@@ -323,11 +642,21 @@ pub struct HookOptions {
     /// Select which git hook(s) to run for.
     /// Default all stages are selected.
     /// See <https://pre-commit.com/#confining-hooks-to-run-at-certain-stages>.
-    pub stages: Option<Vec<Stage>>,
+    #[schemars(with = "Option<Vec<Stage>>")]
+    pub stages: Option<Stages>,
     /// Print the output of the hook even if it passes.
     /// Default is false.
     pub verbose: Option<bool>,
+    /// Stream the output of the hook while it is running, instead of printing it after the hook
+    /// finishes. Implied by `--verbose`.
+    /// Default is false.
+    pub stream: Option<bool>,
+    /// Tags of the hook, which can be selected with `prek run --tag <TAG>`.
+    pub tags: Option<Vec<String>>,
+    /// The minimum version of pre-commit the hook requires.
     pub minimum_pre_commit_version: Option<String>,
+    /// The minimum version of prek the hook requires.
+    pub minimum_prek_version: Option<String>,
 }
 
 impl HookOptions {
@@ -360,7 +689,10 @@ impl HookOptions {
             require_serial,
             stages,
             verbose,
+            stream,
+            tags,
             minimum_pre_commit_version,
+            minimum_prek_version,
         );
     }
 }
@@ -368,7 +700,7 @@ impl HookOptions {
 /// A remote hook in the configuration file.
 ///
 /// All keys in manifest hook dict are valid in a config hook dict, but are optional.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct RemoteHook {
     /// The id of the hook.
@@ -381,6 +713,9 @@ pub struct RemoteHook {
     pub language: Option<Language>,
     #[serde(flatten)]
     pub options: HookOptions,
+    #[serde(flatten)]
+    #[schemars(skip)]
+    pub unknown: UnknownKeys,
 }
 
 /// A local hook in the configuration file.
@@ -388,7 +723,7 @@ pub struct RemoteHook {
 /// It's the same as the manifest hook definition.
 pub type LocalHook = ManifestHook;
 
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum MetaHookID {
     CheckHooksApply,
@@ -420,12 +755,27 @@ impl FromStr for MetaHookID {
     }
 }
 
-/// A meta hook predefined in pre-commit.
+/// A meta hook predefined in pre-commit, only `language: system` is allowed and `entry` can't be set.
 ///
 /// It's the same as the manifest hook definition but with only a few predefined id allowed.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, JsonSchema)]
+#[schemars(with = "MetaHookSchema")]
 pub struct MetaHook(pub(crate) ManifestHook);
 
+#[derive(JsonSchema)]
+#[schemars(extend("not" = { "required": ["entry"] }))]
+#[allow(dead_code)] // Only describes the schema of `MetaHook`.
+struct MetaHookSchema {
+    /// The id of the meta hook.
+    id: MetaHookID,
+    /// Override the name of the hook.
+    name: Option<String>,
+    #[schemars(extend("const" = "system"))]
+    language: Option<Language>,
+    #[serde(flatten)]
+    options: HookOptions,
+}
+
 impl<'de> Deserialize<'de> for MetaHook {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -453,13 +803,15 @@ impl<'de> Deserialize<'de> for MetaHook {
                 language: Language::System,
                 entry: String::new(),
                 options: HookOptions {
-                    files: Some(format!(
-                        "^{}|{}$",
+                    files: Some(FilePattern::regex(format!(
+                        "^({}|{}|{})$",
                         regex::escape(CONFIG_FILE),
-                        regex::escape(ALTER_CONFIG_FILE)
-                    )),
+                        regex::escape(ALTER_CONFIG_FILE),
+                        regex::escape(PREK_TOML)
+                    ))),
                     ..Default::default()
                 },
+                unknown: UnknownKeys::default(),
             },
             MetaHookID::CheckUselessExcludes => ManifestHook {
                 id: MetaHookID::CheckUselessExcludes.to_string(),
@@ -467,13 +819,15 @@ impl<'de> Deserialize<'de> for MetaHook {
                 language: Language::System,
                 entry: String::new(),
                 options: HookOptions {
-                    files: Some(format!(
-                        "^{}|{}$",
+                    files: Some(FilePattern::regex(format!(
+                        "^({}|{}|{})$",
                         regex::escape(CONFIG_FILE),
-                        regex::escape(ALTER_CONFIG_FILE)
-                    )),
+                        regex::escape(ALTER_CONFIG_FILE),
+                        regex::escape(PREK_TOML)
+                    ))),
                     ..Default::default()
                 },
+                unknown: UnknownKeys::default(),
             },
             MetaHookID::Identity => ManifestHook {
                 id: MetaHookID::Identity.to_string(),
@@ -484,10 +838,12 @@ impl<'de> Deserialize<'de> for MetaHook {
                     verbose: Some(true),
                     ..Default::default()
                 },
+                unknown: UnknownKeys::default(),
             },
         };
 
         defaults.options.update(&hook.options);
+        defaults.unknown = hook.unknown;
 
         Ok(MetaHook(defaults))
     }
@@ -499,12 +855,16 @@ impl From<MetaHook> for ManifestHook {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, JsonSchema)]
 pub struct RemoteRepo {
+    /// The URL of the repo to clone.
+    #[schemars(with = "String", extend("not" = { "enum": ["local", "meta"] }))]
     pub repo: Url,
+    /// The revision or tag to clone at.
     pub rev: String,
-    #[serde(skip)]
     pub hooks: Vec<RemoteHook>,
+    #[schemars(skip)]
+    pub unknown: UnknownKeys,
 }
 
 impl PartialEq for RemoteRepo {
@@ -550,13 +910,55 @@ impl Display for MetaRepo {
     }
 }
 
-#[derive(Debug, Clone)]
+/// A repo with hooks, `local` and `meta` repos define the hooks in the config.
+#[derive(Debug, Clone, JsonSchema)]
+#[schemars(untagged)]
 pub enum Repo {
     Remote(RemoteRepo),
+    #[schemars(with = "LocalRepoSchema")]
     Local(LocalRepo),
+    #[schemars(with = "MetaRepoSchema")]
     Meta(MetaRepo),
 }
 
+#[derive(JsonSchema)]
+#[schemars(rename = "LocalRepo", deny_unknown_fields)]
+#[allow(dead_code)] // Only describes the schema of `LocalRepo`.
+struct LocalRepoSchema {
+    #[schemars(with = "String", extend("const" = "local"))]
+    repo: (),
+    hooks: Vec<LocalHook>,
+}
+
+#[derive(JsonSchema)]
+#[schemars(rename = "MetaRepo", deny_unknown_fields)]
+#[allow(dead_code)] // Only describes the schema of `MetaRepo`.
+struct MetaRepoSchema {
+    #[schemars(with = "String", extend("const" = "meta"))]
+    repo: (),
+    hooks: Vec<MetaHook>,
+}
+
+impl Repo {
+    /// Ids of the hooks configured in the repo.
+    pub fn hook_ids(&self) -> Vec<&str> {
+        match self {
+            Repo::Remote(repo) => repo.hooks.iter().map(|h| h.id.as_str()).collect(),
+            Repo::Local(repo) => repo.hooks.iter().map(|h| h.id.as_str()).collect(),
+            Repo::Meta(repo) => repo.hooks.iter().map(|h| h.0.id.as_str()).collect(),
+        }
+    }
+
+    /// Keep only the hooks whose id matches the predicate.
+    fn retain_hooks(&mut self, f: impl Fn(&str) -> bool) {
+        match self {
+            Repo::Remote(repo) => repo.hooks.retain(|h| f(&h.id)),
+            Repo::Local(repo) => repo.hooks.retain(|h| f(&h.id)),
+            Repo::Meta(repo) => repo.hooks.retain(|h| f(&h.0.id)),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for Repo {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -577,14 +979,21 @@ impl<'de> Deserialize<'de> for Repo {
                 struct _RemoteRepo {
                     rev: String,
                     hooks: Vec<RemoteHook>,
+                    #[serde(flatten)]
+                    unknown: UnknownKeys,
                 }
-                let _RemoteRepo { rev, hooks } = _RemoteRepo::deserialize(rest)
+                let _RemoteRepo {
+                    rev,
+                    hooks,
+                    unknown,
+                } = _RemoteRepo::deserialize(rest)
                     .map_err(|e| serde::de::Error::custom(format!("Invalid remote repo: {e}")))?;
 
                 Ok(Repo::Remote(RemoteRepo {
                     repo: url,
                     rev,
                     hooks,
+                    unknown,
                 }))
             }
             RepoLocation::Local => {
@@ -611,7 +1020,7 @@ impl<'de> Deserialize<'de> for Repo {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct ManifestHook {
     /// The id of the hook.
@@ -624,9 +1033,13 @@ pub struct ManifestHook {
     pub language: Language,
     #[serde(flatten)]
     pub options: HookOptions,
+    #[serde(flatten)]
+    #[schemars(skip)]
+    pub unknown: UnknownKeys,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// A `.pre-commit-hooks.yaml` manifest file, listing the hooks a repo provides.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(transparent)]
 pub struct Manifest {
@@ -644,12 +1057,263 @@ pub enum Error {
     #[error("Failed to parse `{0}`")]
     Yaml(String, #[source] serde_yaml::Error),
 
+    #[error("Failed to parse `{0}`")]
+    Toml(String, #[source] toml::de::Error),
+
     #[error("Invalid repo URL: {0}")]
     RepoUrl(#[from] url::ParseError),
+
+    #[error("Config `{0}` is not supported by this version of prek")]
+    UnsupportedVersion(String, #[source] MinimumVersionError),
+
+    #[error("Invalid file pattern in `{0}`")]
+    Pattern(String, #[source] Box<LocatedError>),
+}
+
+/// Keys that are not recognized, collected to warn about them.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+#[allow(clippy::zero_sized_map_values)] // Only the keys are kept.
+pub struct UnknownKeys(BTreeMap<String, IgnoredAny>);
+
+impl UnknownKeys {
+    /// Describe each unknown key, suggesting the closest valid key.
+    fn describe<'a>(
+        &'a self,
+        location: &'a str,
+        valid_keys: &'a [String],
+    ) -> impl Iterator<Item = String> + 'a {
+        self.0.keys().map(move |key| {
+            let mut message = format!("Unknown key `{key}` in {location}");
+            if let Some(closest) = closest_key(key, valid_keys) {
+                write!(message, ", did you mean `{closest}`?").unwrap();
+            }
+            message
+        })
+    }
 }
 
-/// Read the configuration file from the given path.
+/// The keys of an object in the config, from the properties of its schema.
+fn schema_keys<T: JsonSchema>() -> Vec<String> {
+    schemars::schema_for!(T)
+        .get("properties")
+        .and_then(serde_json::Value::as_object)
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+static CONFIG_KEYS: LazyLock<Vec<String>> = LazyLock::new(schema_keys::<Config>);
+static REMOTE_REPO_KEYS: LazyLock<Vec<String>> = LazyLock::new(schema_keys::<RemoteRepo>);
+static HOOK_KEYS: LazyLock<Vec<String>> = LazyLock::new(schema_keys::<RemoteHook>);
+
+/// Find the valid key closest to an unknown key, if any is close enough.
+fn closest_key<'a>(key: &str, valid_keys: &'a [String]) -> Option<&'a str> {
+    valid_keys
+        .iter()
+        .map(|valid| (strsim::jaro_winkler(key, valid), valid.as_str()))
+        .filter(|(similarity, _)| *similarity > 0.8)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, valid)| valid)
+}
+
+/// Whether a quantified group contains another quantifier, like `(a+)+` or `(.*)*`.
+///
+/// Such patterns can backtrack catastrophically with backtracking engines, like Python's `re`
+/// used by pre-commit, or `fancy_regex` when the pattern uses lookarounds or backreferences.
+fn nested_quantifier(pattern: &str) -> bool {
+    // Whether each open group contains a quantifier.
+    let mut groups = vec![false];
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => {
+                // Skip the character class, `]` is literal right after `[` or `[^`.
+                chars.next_if_eq(&'^');
+                chars.next_if_eq(&']');
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        ']' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '(' => groups.push(false),
+            ')' if groups.len() > 1 => {
+                let inner = groups.pop().unwrap_or_default();
+                if matches!(chars.peek(), Some('*' | '+' | '{')) {
+                    if inner {
+                        return true;
+                    }
+                    if let Some(outer) = groups.last_mut() {
+                        *outer = true;
+                    }
+                }
+            }
+            '*' | '+' | '{' => {
+                if let Some(group) = groups.last_mut() {
+                    *group = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+impl Config {
+    /// Compile the `files` and `exclude` patterns of the config and its hooks.
+    ///
+    /// Returns warnings for patterns that may backtrack catastrophically.
+    fn check_patterns(&self, path: &Path) -> Result<Vec<String>, Error> {
+        let file = path.user_display().to_string();
+        let mut warnings = Vec::new();
+        let mut check = |pattern: Option<&FilePattern>, key: &'static str, hook: Option<&str>| {
+            let Some(pattern) = pattern else {
+                return Ok(());
+            };
+            let (location, description) = match hook {
+                Some(hook) => (
+                    Location::HookKey {
+                        hook: hook.to_string(),
+                        key,
+                    },
+                    format!("`{key}` of hook `{hook}`"),
+                ),
+                None => (Location::Key(key), format!("`{key}`")),
+            };
+            if let Err(err) = pattern.compile() {
+                let kind = match pattern {
+                    FilePattern::Regex(_) => "regex",
+                    FilePattern::Glob { .. } => "glob",
+                };
+                let error = LocatedError::new(
+                    path,
+                    location,
+                    err.to_string(),
+                    format!("{description} is not a valid {kind}: {err}"),
+                );
+                return Err(Error::Pattern(file.clone(), Box::new(error)));
+            }
+            if let FilePattern::Regex(pattern) = pattern
+                && nested_quantifier(pattern)
+            {
+                warnings.push(format!(
+                    "Regex `{pattern}` in {description} of `{file}` has nested quantifiers, which may backtrack catastrophically"
+                ));
+            }
+            Ok(())
+        };
+
+        check(self.files.as_ref(), "files", None)?;
+        check(self.exclude.as_ref(), "exclude", None)?;
+        for repo in &self.repos {
+            let hooks: Vec<(&str, &HookOptions)> = match repo {
+                Repo::Remote(repo) => repo.hooks.iter().map(|h| (&*h.id, &h.options)).collect(),
+                Repo::Local(repo) => repo.hooks.iter().map(|h| (&*h.id, &h.options)).collect(),
+                Repo::Meta(repo) => repo
+                    .hooks
+                    .iter()
+                    .map(|h| (&*h.0.id, &h.0.options))
+                    .collect(),
+            };
+            for (id, options) in hooks {
+                check(options.files.as_ref(), "files", Some(id))?;
+                check(options.exclude.as_ref(), "exclude", Some(id))?;
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    /// Describe the unknown keys and deprecated stage names at every level of the config.
+    fn describe_warnings(&self, file: &str) -> Vec<String> {
+        let location = format!("`{file}`");
+        let mut messages: Vec<String> = self.unknown.describe(&location, &CONFIG_KEYS).collect();
+        if let Some(stages) = &self.default_stages {
+            let location = format!("`default_stages` of `{file}`");
+            messages.extend(stages.describe_deprecated(&location));
+        }
+
+        for repo in &self.repos {
+            let hooks: Vec<(&str, &HookOptions, &UnknownKeys)> = match repo {
+                Repo::Remote(repo) => {
+                    let location = format!("repo `{}` of `{file}`", repo.repo);
+                    messages.extend(repo.unknown.describe(&location, &REMOTE_REPO_KEYS));
+                    repo.hooks
+                        .iter()
+                        .map(|h| (&*h.id, &h.options, &h.unknown))
+                        .collect()
+                }
+                Repo::Local(repo) => repo
+                    .hooks
+                    .iter()
+                    .map(|h| (&*h.id, &h.options, &h.unknown))
+                    .collect(),
+                Repo::Meta(repo) => repo
+                    .hooks
+                    .iter()
+                    .map(|h| (&*h.0.id, &h.0.options, &h.0.unknown))
+                    .collect(),
+            };
+            for (id, options, unknown) in hooks {
+                let location = format!("hook `{id}` of `{file}`");
+                messages.extend(unknown.describe(&location, &HOOK_KEYS));
+                if let Some(stages) = &options.stages {
+                    messages.extend(stages.describe_deprecated(&location));
+                }
+            }
+        }
+
+        messages
+    }
+}
+
+/// The format of a config file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ConfigFormat {
+    #[default]
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Detect the format from the file extension, `.toml` files are TOML, others are YAML.
+    pub fn from_path(path: &Path) -> Self {
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            Self::Toml
+        } else {
+            Self::Yaml
+        }
+    }
+
+    /// The default config file name of the format.
+    pub fn default_file(self) -> &'static str {
+        match self {
+            Self::Yaml => CONFIG_FILE,
+            Self::Toml => PREK_TOML,
+        }
+    }
+}
+
+/// Read the configuration file from the given path, warning about unknown keys and deprecated
+/// values.
 pub fn read_config(path: &Path) -> Result<Config, Error> {
+    let (config, warnings) = read_config_with_warnings(path)?;
+    for warning in warnings {
+        warn_user_once!("{warning}");
+    }
+    Ok(config)
+}
+
+/// Read the configuration file from the given path, and describe its unknown keys and
+/// deprecated values.
+pub fn read_config_with_warnings(path: &Path) -> Result<(Config, Vec<String>), Error> {
     let content = match fs_err::read_to_string(path) {
         Ok(content) => content,
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
@@ -657,9 +1321,24 @@ pub fn read_config(path: &Path) -> Result<Config, Error> {
         }
         Err(e) => return Err(e.into()),
     };
-    let config = serde_yaml::from_str(&content)
-        .map_err(|e| Error::Yaml(path.user_display().to_string(), e))?;
-    Ok(config)
+    let config: Config = match ConfigFormat::from_path(path) {
+        ConfigFormat::Yaml => serde_yaml::from_str(&content)
+            .map_err(|e| Error::Yaml(path.user_display().to_string(), e))?,
+        ConfigFormat::Toml => {
+            toml::from_str(&content).map_err(|e| Error::Toml(path.user_display().to_string(), e))?
+        }
+    };
+
+    check_minimum_versions(
+        config.minimum_pre_commit_version.as_deref(),
+        config.minimum_prek_version.as_deref(),
+    )
+    .map_err(|e| Error::UnsupportedVersion(path.user_display().to_string(), e))?;
+
+    let file = path.user_display().to_string();
+    let mut warnings = config.describe_warnings(&file);
+    warnings.extend(config.check_patterns(path)?);
+    Ok((config, warnings))
 }
 
 /// Read the manifest file from the given path.
@@ -717,8 +1396,14 @@ mod tests {
                                         require_serial: None,
                                         stages: None,
                                         verbose: None,
+                                        stream: None,
+                                        tags: None,
                                         minimum_pre_commit_version: None,
+                                        minimum_prek_version: None,
                                     },
+                                    unknown: UnknownKeys(
+                                        {},
+                                    ),
                                 },
                             ],
                         },
@@ -731,7 +1416,14 @@ mod tests {
                 exclude: None,
                 fail_fast: None,
                 minimum_pre_commit_version: None,
+                minimum_prek_version: None,
                 ci: None,
+                groups: None,
+                extends: None,
+                disabled_hooks: None,
+                unknown: UnknownKeys(
+                    {},
+                ),
             },
         )
         "#);
@@ -808,10 +1500,19 @@ mod tests {
                                         require_serial: None,
                                         stages: None,
                                         verbose: None,
+                                        stream: None,
+                                        tags: None,
                                         minimum_pre_commit_version: None,
+                                        minimum_prek_version: None,
                                     },
+                                    unknown: UnknownKeys(
+                                        {},
+                                    ),
                                 },
                             ],
+                            unknown: UnknownKeys(
+                                {},
+                            ),
                         },
                     ),
                 ],
@@ -822,7 +1523,14 @@ mod tests {
                 exclude: None,
                 fail_fast: None,
                 minimum_pre_commit_version: None,
+                minimum_prek_version: None,
                 ci: None,
+                groups: None,
+                extends: None,
+                disabled_hooks: None,
+                unknown: UnknownKeys(
+                    {},
+                ),
             },
         )
         "#);
@@ -917,8 +1625,14 @@ mod tests {
                                         require_serial: None,
                                         stages: None,
                                         verbose: None,
+                                        stream: None,
+                                        tags: None,
                                         minimum_pre_commit_version: None,
+                                        minimum_prek_version: None,
                                     },
+                                    unknown: UnknownKeys(
+                                        {},
+                                    ),
                                 },
                             ],
                         },
@@ -931,7 +1645,14 @@ mod tests {
                 exclude: None,
                 fail_fast: None,
                 minimum_pre_commit_version: None,
+                minimum_prek_version: None,
                 ci: None,
+                groups: None,
+                extends: None,
+                disabled_hooks: None,
+                unknown: UnknownKeys(
+                    {},
+                ),
             },
         )
         "#);
@@ -1025,7 +1746,9 @@ mod tests {
                                         options: HookOptions {
                                             alias: None,
                                             files: Some(
-                                                "^\\.pre-commit-config\\.yaml|\\.pre-commit-config\\.yml$",
+                                                Regex(
+                                                    "^(\\.pre-commit-config\\.yaml|\\.pre-commit-config\\.yml|prek\\.toml)$",
+                                                ),
                                             ),
                                             exclude: None,
                                             types: None,
@@ -1042,8 +1765,14 @@ mod tests {
                                             require_serial: None,
                                             stages: None,
                                             verbose: None,
+                                            stream: None,
+                                            tags: None,
                                             minimum_pre_commit_version: None,
+                                            minimum_prek_version: None,
                                         },
+                                        unknown: UnknownKeys(
+                                            {},
+                                        ),
                                     },
                                 ),
                                 MetaHook(
@@ -1055,7 +1784,9 @@ mod tests {
                                         options: HookOptions {
                                             alias: None,
                                             files: Some(
-                                                "^\\.pre-commit-config\\.yaml|\\.pre-commit-config\\.yml$",
+                                                Regex(
+                                                    "^(\\.pre-commit-config\\.yaml|\\.pre-commit-config\\.yml|prek\\.toml)$",
+                                                ),
                                             ),
                                             exclude: None,
                                             types: None,
@@ -1072,8 +1803,14 @@ mod tests {
                                             require_serial: None,
                                             stages: None,
                                             verbose: None,
+                                            stream: None,
+                                            tags: None,
                                             minimum_pre_commit_version: None,
+                                            minimum_prek_version: None,
                                         },
+                                        unknown: UnknownKeys(
+                                            {},
+                                        ),
                                     },
                                 ),
                                 MetaHook(
@@ -1102,8 +1839,14 @@ mod tests {
                                             verbose: Some(
                                                 true,
                                             ),
+                                            stream: None,
+                                            tags: None,
                                             minimum_pre_commit_version: None,
+                                            minimum_prek_version: None,
                                         },
+                                        unknown: UnknownKeys(
+                                            {},
+                                        ),
                                     },
                                 ),
                             ],
@@ -1117,7 +1860,14 @@ mod tests {
                 exclude: None,
                 fail_fast: None,
                 minimum_pre_commit_version: None,
+                minimum_prek_version: None,
                 ci: None,
+                groups: None,
+                extends: None,
+                disabled_hooks: None,
+                unknown: UnknownKeys(
+                    {},
+                ),
             },
         )
         "#);
@@ -1178,8 +1928,14 @@ mod tests {
                                         require_serial: None,
                                         stages: None,
                                         verbose: None,
+                                        stream: None,
+                                        tags: None,
                                         minimum_pre_commit_version: None,
+                                        minimum_prek_version: None,
                                     },
+                                    unknown: UnknownKeys(
+                                        {},
+                                    ),
                                 },
                                 ManifestHook {
                                     id: "hook-2",
@@ -1206,8 +1962,14 @@ mod tests {
                                         require_serial: None,
                                         stages: None,
                                         verbose: None,
+                                        stream: None,
+                                        tags: None,
                                         minimum_pre_commit_version: None,
+                                        minimum_prek_version: None,
                                     },
+                                    unknown: UnknownKeys(
+                                        {},
+                                    ),
                                 },
                                 ManifestHook {
                                     id: "hook-3",
@@ -1234,8 +1996,14 @@ mod tests {
                                         require_serial: None,
                                         stages: None,
                                         verbose: None,
+                                        stream: None,
+                                        tags: None,
                                         minimum_pre_commit_version: None,
+                                        minimum_prek_version: None,
                                     },
+                                    unknown: UnknownKeys(
+                                        {},
+                                    ),
                                 },
                             ],
                         },
@@ -1248,7 +2016,14 @@ mod tests {
                 exclude: None,
                 fail_fast: None,
                 minimum_pre_commit_version: None,
+                minimum_prek_version: None,
                 ci: None,
+                groups: None,
+                extends: None,
+                disabled_hooks: None,
+                unknown: UnknownKeys(
+                    {},
+                ),
             },
         )
         "#);
@@ -1267,4 +2042,175 @@ mod tests {
         insta::assert_debug_snapshot!(manifest);
         Ok(())
     }
+
+    #[test]
+    fn parse_config_base() {
+        assert_eq!(
+            ConfigBase::from_str("../base.yaml").unwrap(),
+            ConfigBase::Local(PathBuf::from("../base.yaml"))
+        );
+        assert_eq!(
+            ConfigBase::from_str("https://github.com/org/configs@v1.0.0:python/base.yaml").unwrap(),
+            ConfigBase::Remote {
+                repo: Url::parse("https://github.com/org/configs").unwrap(),
+                rev: "v1.0.0".to_string(),
+                path: "python/base.yaml".to_string(),
+            }
+        );
+        assert!(ConfigBase::from_str("https://github.com/org/configs@v1.0.0").is_err());
+        assert!(ConfigBase::from_str("https://github.com/org/configs:base.yaml").is_err());
+    }
+
+    #[test]
+    fn inherit_config() -> Result<()> {
+        let base: Config = serde_yaml::from_str(indoc::indoc! {r"
+            fail_fast: true
+            exclude: ^vendor/
+            minimum_prek_version: '0.10.0'
+            repos:
+              - repo: local
+                hooks:
+                  - id: a
+                    name: a
+                    entry: a
+                    language: system
+                  - id: b
+                    name: b
+                    entry: b
+                    language: system
+                  - id: c
+                    name: c
+                    entry: c
+                    language: system
+        "})?;
+        let mut config: Config = serde_yaml::from_str(indoc::indoc! {r"
+            fail_fast: false
+            minimum_prek_version: '0.2.0'
+            disabled_hooks: [c]
+            repos:
+              - repo: local
+                hooks:
+                  - id: b
+                    name: b
+                    entry: b --override
+                    language: system
+        "})?;
+        config.inherit(base);
+
+        let hooks = config
+            .repos
+            .iter()
+            .flat_map(|repo| match repo {
+                Repo::Local(repo) => repo.hooks.iter().map(|h| h.entry.as_str()),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(hooks, ["a", "b --override"]);
+        assert_eq!(config.fail_fast, Some(false));
+        assert_eq!(config.exclude, Some(FilePattern::regex("^vendor/")));
+        assert_eq!(config.minimum_prek_version.as_deref(), Some("0.10.0"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_quantifiers() {
+        assert!(nested_quantifier("(a+)+"));
+        assert!(nested_quantifier("^(.*)*$"));
+        assert!(nested_quantifier("((ab)*c)+"));
+        assert!(nested_quantifier("(?:[a-z]+/){2,}"));
+
+        assert!(!nested_quantifier(r"^src/.*\.rs$"));
+        assert!(!nested_quantifier("(foo|bar)+"));
+        assert!(!nested_quantifier(r"(a\+)+"));
+        assert!(!nested_quantifier("([+*])+"));
+        assert!(!nested_quantifier("(a+)b"));
+    }
+
+    #[test]
+    fn meta_hooks_files() -> Result<()> {
+        let yaml = indoc::indoc! { r"
+            repos:
+              - repo: meta
+                hooks:
+                  - id: check-hooks-apply
+        "};
+        let config = serde_yaml::from_str::<Config>(yaml)?;
+        let Repo::Meta(repo) = &config.repos[0] else {
+            panic!("expected a meta repo");
+        };
+        let files = repo.hooks[0].0.options.files.as_ref().expect("files set");
+        let matcher = files.compile()?;
+
+        assert!(matcher.is_match(".pre-commit-config.yaml"));
+        assert!(matcher.is_match(".pre-commit-config.yml"));
+        assert!(matcher.is_match("prek.toml"));
+        assert!(!matcher.is_match(".pre-commit-config.yaml.bak"));
+        assert!(!matcher.is_match("docs/prek.toml"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn valid_keys() {
+        assert!(CONFIG_KEYS.iter().any(|key| key == "disabled_hooks"));
+        assert!(!CONFIG_KEYS.iter().any(|key| key == "unknown"));
+        assert_eq!(*REMOTE_REPO_KEYS, ["hooks", "repo", "rev"]);
+        assert!(HOOK_KEYS.iter().any(|key| key == "id"));
+        assert!(HOOK_KEYS.iter().any(|key| key == "stages"));
+        assert!(!HOOK_KEYS.iter().any(|key| key == "unknown"));
+    }
+
+    #[test]
+    fn deprecated_stages() -> Result<()> {
+        let yaml = indoc::indoc! {r"
+            default_stages: [commit, manual]
+            repos: []
+        "};
+        let config: Config = serde_yaml::from_str(yaml)?;
+        let stages = config.default_stages.expect("default_stages set");
+        assert_eq!(
+            stages
+                .describe_deprecated("`default_stages`")
+                .collect::<Vec<_>>(),
+            ["Stage `commit` in `default_stages` is deprecated, use `pre-commit` instead"]
+        );
+        assert_eq!(Vec::from(stages), [Stage::PreCommit, Stage::Manual]);
+
+        let err =
+            serde_yaml::from_str::<Config>("default_stages: [commits]\nrepos: []\n").unwrap_err();
+        assert!(
+            err.to_string().contains("unknown variant `commits`"),
+            "{err}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn json_schema() -> Result<()> {
+        let schema = serde_json::to_value(schemars::schema_for!(Config))?;
+        let defs = &schema["$defs"];
+        assert_eq!(defs["Repo"]["anyOf"].as_array().map(Vec::len), Some(3));
+        assert_eq!(defs["LocalRepo"]["properties"]["repo"]["const"], "local");
+        assert_eq!(defs["MetaRepo"]["properties"]["repo"]["const"], "meta");
+        assert_eq!(
+            defs["MetaHook"]["properties"]["language"]["const"],
+            "system"
+        );
+        assert_eq!(defs["MetaHook"]["not"]["required"][0], "entry");
+        assert!(
+            defs["MetaHookID"]["enum"]
+                .as_array()
+                .is_some_and(|ids| ids.len() == 3)
+        );
+        assert!(schema["properties"]["ci"].is_object());
+        assert!(schema["properties"].get("unknown").is_none());
+
+        let schema = serde_json::to_value(schemars::schema_for!(Manifest))?;
+        assert_eq!(schema["type"], "array");
+        assert_eq!(schema["items"]["$ref"], "#/$defs/ManifestHook");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,411 @@
+use std::error::Error;
+use std::fmt;
+use std::iter;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use miette::{Diagnostic, GraphicalReportHandler, GraphicalTheme, NamedSource, SourceSpan};
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
+use crate::config::{self, ConfigFormat};
+use crate::fs::Simplified;
+use crate::hook;
+use crate::workspace;
+
+/// A location in a config file.
+#[derive(Debug, Clone)]
+pub(crate) enum Location {
+    /// A byte offset, the span covers the token starting at it.
+    Offset(usize),
+    /// A top-level key.
+    Key(&'static str),
+    /// The id of a hook.
+    Hook(String),
+    /// A key of a hook, falls back to the hook id if the key is not set in the file.
+    HookKey { hook: String, key: &'static str },
+}
+
+impl Location {
+    /// Find the location in the source text of the config file at `path`.
+    ///
+    /// Keys and hooks are looked up in the parsed document, so flow-style YAML and
+    /// inline TOML tables work as well.
+    fn find(&self, path: &Path, source: &str) -> Option<Range<usize>> {
+        if let Self::Offset(offset) = self {
+            let offset = (*offset).min(source.len());
+            let len = source[offset..]
+                .find(char::is_whitespace)
+                .unwrap_or(source.len() - offset);
+            return Some(offset..offset + len);
+        }
+        match ConfigFormat::from_path(path) {
+            ConfigFormat::Yaml => self.find_yaml(source),
+            ConfigFormat::Toml => self.find_toml(source),
+        }
+    }
+
+    fn find_yaml(&self, source: &str) -> Option<Range<usize>> {
+        match self {
+            Self::Offset(_) => None,
+            Self::Key(key) => {
+                let offset = yaml_offset(source, &[Step::Key(key)], false)?;
+                Some(token(source, offset, key.len()))
+            }
+            Self::Hook(hook) => {
+                let (repo, index) = yaml_hook(source, hook)?;
+                let path = hook_path(repo, index, "id");
+                let offset = yaml_offset(source, &path, true)?;
+                Some(token(source, offset, hook.len()))
+            }
+            Self::HookKey { hook, key } => {
+                let (repo, index) = yaml_hook(source, hook)?;
+                match yaml_offset(source, &hook_path(repo, index, key), false) {
+                    Some(offset) => Some(token(source, offset, key.len())),
+                    None => Self::Hook(hook.clone()).find_yaml(source),
+                }
+            }
+        }
+    }
+
+    fn find_toml(&self, source: &str) -> Option<Range<usize>> {
+        let document = toml_edit::ImDocument::parse(source).ok()?;
+        let root = document.as_table();
+        let span = match self {
+            Self::Offset(_) => None,
+            Self::Key(key) => root.key(key).and_then(toml_edit::Key::span),
+            Self::Hook(hook) => toml_hook(root, hook)?.get("id").and_then(toml_span),
+            Self::HookKey { hook, key } => {
+                let table = toml_hook(root, hook)?;
+                table
+                    .key(key)
+                    .and_then(toml_edit::Key::span)
+                    .or_else(|| table.get("id").and_then(toml_span))
+            }
+        }?;
+        Some(token(source, span.start, span.len()))
+    }
+}
+
+/// The span of `len` bytes at `offset`, excluding the quotes of a quoted scalar.
+fn token(source: &str, offset: usize, len: usize) -> Range<usize> {
+    let quoted = source[offset..].starts_with(['"', '\'']);
+    let start = if quoted { offset + 1 } else { offset };
+    let len = if quoted && len >= 2 && source[offset..offset + len].ends_with(['"', '\'']) {
+        len - 2
+    } else {
+        len
+    };
+    start..start + len
+}
+
+/// The path from the document root to the `key` of the hook at `repos[repo].hooks[index]`.
+fn hook_path(repo: usize, index: usize, key: &str) -> [Step<'_>; 5] {
+    [
+        Step::Key("repos"),
+        Step::Index(repo),
+        Step::Key("hooks"),
+        Step::Index(index),
+        Step::Key(key),
+    ]
+}
+
+/// The indices of the repo and the hook with the given id in a YAML config.
+fn yaml_hook(source: &str, id: &str) -> Option<(usize, usize)> {
+    let document: serde_yaml::Value = serde_yaml::from_str(source).ok()?;
+    let repos = document.get("repos")?.as_sequence()?;
+    repos.iter().enumerate().find_map(|(repo, config)| {
+        let hooks = config.get("hooks")?.as_sequence()?;
+        hooks
+            .iter()
+            .position(|hook| hook.get("id").and_then(serde_yaml::Value::as_str) == Some(id))
+            .map(|index| (repo, index))
+    })
+}
+
+/// The byte offset of the key at the end of `path` in a YAML document, or of its value if
+/// `value` is set and the value is a string.
+///
+/// `serde_yaml` doesn't expose spans, but the errors it reports carry the position of the
+/// node being deserialized, so the document is walked until the node is reached and an
+/// error is raised there.
+fn yaml_offset(source: &str, path: &[Step], value: bool) -> Option<usize> {
+    let deserializer = serde_yaml::Deserializer::from_str(source);
+    let err = Probe { path, value }.deserialize(deserializer).err()?;
+    // The message is prefixed with the path of the node.
+    if !err.to_string().contains(FOUND) {
+        return None;
+    }
+    Some(err.location()?.index())
+}
+
+/// The message of the error raised when the probed node is reached.
+const FOUND: &str = "found the probed node";
+
+/// A step on the path to a node in a YAML document.
+#[derive(Debug, Clone, Copy)]
+enum Step<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Walks a YAML document along a path, failing with [`FOUND`] at the node it points to.
+#[derive(Clone, Copy)]
+struct Probe<'a> {
+    path: &'a [Step<'a>],
+    value: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for Probe<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        match self.path.first() {
+            None => deserializer.deserialize_str(Found),
+            Some(Step::Key(_)) => deserializer.deserialize_map(self),
+            Some(Step::Index(_)) => deserializer.deserialize_seq(self),
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for Probe<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a mapping or a sequence")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let [Step::Key(key), rest @ ..] = self.path else {
+            return Ok(());
+        };
+        let target = rest.is_empty() && !self.value;
+        while let Some(matched) = map.next_key_seed(KeyProbe { key, target })? {
+            if matched {
+                map.next_value_seed(Probe {
+                    path: rest,
+                    value: self.value,
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let [Step::Index(index), rest @ ..] = self.path else {
+            return Ok(());
+        };
+        let mut current = 0;
+        loop {
+            let next = if current == *index {
+                seq.next_element_seed(Probe {
+                    path: rest,
+                    value: self.value,
+                })?
+            } else {
+                seq.next_element::<IgnoredAny>()?.map(|_| ())
+            };
+            if next.is_none() {
+                return Ok(());
+            }
+            current += 1;
+        }
+    }
+}
+
+/// Matches a mapping key, failing with [`FOUND`] if it's the `target`.
+struct KeyProbe<'a> {
+    key: &'a str,
+    target: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for KeyProbe<'_> {
+    type Value = bool;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for KeyProbe<'_> {
+    type Value = bool;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string key")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<bool, E> {
+        if v != self.key {
+            Ok(false)
+        } else if self.target {
+            Err(E::custom(FOUND))
+        } else {
+            Ok(true)
+        }
+    }
+}
+
+/// Fails with [`FOUND`] on a string value.
+struct Found;
+
+impl Visitor<'_> for Found {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, _v: &str) -> Result<(), E> {
+        Err(E::custom(FOUND))
+    }
+}
+
+/// The tables of an array of tables, or the inline tables of an array.
+fn toml_tables(item: &toml_edit::Item) -> Vec<&dyn toml_edit::TableLike> {
+    match item {
+        toml_edit::Item::ArrayOfTables(tables) => tables
+            .iter()
+            .map(|table| table as &dyn toml_edit::TableLike)
+            .collect(),
+        toml_edit::Item::Value(toml_edit::Value::Array(array)) => array
+            .iter()
+            .filter_map(toml_edit::Value::as_inline_table)
+            .map(|table| table as &dyn toml_edit::TableLike)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The table of the hook with the given id in a TOML config.
+fn toml_hook<'a>(root: &'a toml_edit::Table, id: &str) -> Option<&'a dyn toml_edit::TableLike> {
+    toml_tables(root.get("repos")?)
+        .into_iter()
+        .flat_map(|repo| repo.get("hooks").map(toml_tables).unwrap_or_default())
+        .find(|hook| hook.get("id").and_then(toml_edit::Item::as_str) == Some(id))
+}
+
+fn toml_span(item: &toml_edit::Item) -> Option<Range<usize>> {
+    match item {
+        toml_edit::Item::Value(value) => value.span(),
+        _ => None,
+    }
+}
+
+/// An error that points to a location in a config file.
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub(crate) struct LocatedError {
+    path: PathBuf,
+    location: Location,
+    label: String,
+    message: String,
+}
+
+impl LocatedError {
+    pub(crate) fn new(
+        path: &Path,
+        location: Location,
+        label: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            location,
+            label: label.into(),
+            message: message.into(),
+        }
+    }
+
+    fn locate(&self) -> (PathBuf, Location, String) {
+        (self.path.clone(), self.location.clone(), self.label.clone())
+    }
+}
+
+/// The source snippet to render, the message is printed with the error chain.
+#[derive(Debug, thiserror::Error, Diagnostic)]
+#[error("")]
+struct Snippet {
+    #[source_code]
+    source_code: NamedSource<String>,
+    #[label("{label}")]
+    span: SourceSpan,
+    label: String,
+}
+
+/// Find where an error points to in a config file.
+fn locate(err: &(dyn Error + 'static)) -> Option<(PathBuf, Location, String)> {
+    if let Some(err) = err.downcast_ref::<LocatedError>() {
+        return Some(err.locate());
+    }
+
+    // Config errors are often wrapped transparently, so they don't show up in the chain.
+    let config_error = err.downcast_ref::<config::Error>().or_else(|| {
+        match err.downcast_ref::<workspace::Error>()? {
+            workspace::Error::InvalidConfig(err)
+            | workspace::Error::Hook(hook::Error::InvalidConfig(err)) => Some(err),
+            _ => None,
+        }
+    });
+    if let Some(err) = config_error {
+        return match err {
+            config::Error::Yaml(path, err) => {
+                let offset = err.location()?.index();
+                // The position is already shown in the snippet.
+                let message = err.to_string();
+                let label = message
+                    .rfind(" at line ")
+                    .map_or(message.as_str(), |idx| &message[..idx]);
+                Some((
+                    PathBuf::from(path),
+                    Location::Offset(offset),
+                    label.to_string(),
+                ))
+            }
+            config::Error::Pattern(_, err) => Some(err.locate()),
+            config::Error::UnsupportedVersion(path, err) => Some((
+                PathBuf::from(path),
+                Location::Key(err.key()),
+                err.label().to_string(),
+            )),
+            // TOML parse errors already come with a snippet.
+            _ => None,
+        };
+    }
+
+    if let Some(workspace::Error::HookNotFound { hook, repo, config }) =
+        err.downcast_ref::<workspace::Error>()
+    {
+        return Some((
+            config.clone(),
+            Location::Hook(hook.clone()),
+            format!("not present in `{repo}`"),
+        ));
+    }
+
+    None
+}
+
+/// Render a labelled source snippet for the first error in the chain that points
+/// to a location in a config file.
+pub(crate) fn render(err: &(dyn Error + 'static)) -> Option<String> {
+    let (path, location, label) =
+        iter::successors(Some(err), |&err| err.source()).find_map(locate)?;
+    let source = fs_err::read_to_string(&path).ok()?;
+    let span = location.find(&path, &source)?;
+
+    let snippet = Snippet {
+        source_code: NamedSource::new(path.user_display().to_string(), source),
+        span: span.into(),
+        label,
+    };
+    let mut output = String::new();
+    GraphicalReportHandler::new_themed(GraphicalTheme::unicode())
+        .without_cause_chain()
+        .render_report(&mut output, &snippet)
+        .ok()?;
+
+    // Skip the header, which only contains the empty message.
+    let (_, snippet) = output.split_once('\n')?;
+    Some(snippet.trim_end().to_string())
+}
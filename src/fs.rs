@@ -36,13 +36,23 @@ pub struct LockedFile(fs_err::File);
 
 impl LockedFile {
     /// Inner implementation for [`LockedFile::acquire_blocking`] and [`LockedFile::acquire`].
-    fn lock_file_blocking(file: fs_err::File, resource: &str) -> Result<Self, std::io::Error> {
+    fn lock_file_blocking(
+        file: fs_err::File,
+        resource: &str,
+        shared: bool,
+    ) -> Result<Self, std::io::Error> {
         trace!(
             resource,
+            shared,
             path = %file.path().display(),
             "Checking lock",
         );
-        match file.file().try_lock() {
+        let locked = if shared {
+            file.file().try_lock_shared()
+        } else {
+            file.file().try_lock()
+        };
+        match locked {
             Ok(()) => {
                 debug!(resource, "Acquired lock");
                 Ok(Self(file))
@@ -57,7 +67,12 @@ impl LockedFile {
                     path = %file.path().display(),
                     "Waiting to acquire lock",
                 );
-                file.file().lock().map_err(|err| {
+                let locked = if shared {
+                    file.file().lock_shared()
+                } else {
+                    file.file().lock()
+                };
+                locked.map_err(|err| {
                     // Not a fs_err method, we need to build our own path context
                     std::io::Error::other(format!(
                         "Could not acquire lock for `{resource}` at `{}`: {}",
@@ -81,7 +96,7 @@ impl LockedFile {
     ) -> Result<Self, std::io::Error> {
         let file = fs_err::File::create(path.as_ref())?;
         let resource = resource.to_string();
-        Self::lock_file_blocking(file, &resource)
+        Self::lock_file_blocking(file, &resource, false)
     }
 
     /// Acquire a cross-process lock for a resource using a file at the provided path.
@@ -91,7 +106,36 @@ impl LockedFile {
     ) -> Result<Self, std::io::Error> {
         let file = fs_err::File::create(path.as_ref())?;
         let resource = resource.to_string();
-        tokio::task::spawn_blocking(move || Self::lock_file_blocking(file, &resource)).await?
+        tokio::task::spawn_blocking(move || Self::lock_file_blocking(file, &resource, false))
+            .await?
+    }
+
+    /// Acquire a shared cross-process lock for a resource, which can be held by many processes
+    /// at once, but not while another process holds the exclusive lock.
+    pub async fn acquire_shared(
+        path: impl AsRef<Path>,
+        resource: impl Display,
+    ) -> Result<Self, std::io::Error> {
+        let file = fs_err::File::create(path.as_ref())?;
+        let resource = resource.to_string();
+        tokio::task::spawn_blocking(move || Self::lock_file_blocking(file, &resource, true)).await?
+    }
+
+    /// Acquire a cross-process lock for a resource without waiting, returns `None` if another
+    /// process holds it.
+    pub fn try_acquire(
+        path: impl AsRef<Path>,
+        resource: impl Display,
+    ) -> Result<Option<Self>, std::io::Error> {
+        let file = fs_err::File::create(path.as_ref())?;
+        match file.file().try_lock() {
+            Ok(()) => {
+                debug!(resource = %resource, "Acquired lock");
+                Ok(Some(Self(file)))
+            }
+            Err(std::fs::TryLockError::WouldBlock) => Ok(None),
+            Err(std::fs::TryLockError::Error(err)) => Err(err),
+        }
     }
 }
 
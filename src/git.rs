@@ -259,6 +259,18 @@ pub async fn get_root() -> Result<PathBuf, Error> {
     ))
 }
 
+/// Get the commit checked out in the repo at `path`.
+pub async fn get_head_commit(path: &Path) -> Result<String, Error> {
+    let output = git_cmd("get head commit")?
+        .current_dir(path)
+        .arg("rev-parse")
+        .arg("HEAD")
+        .check(true)
+        .output()
+        .await?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 pub async fn is_dirty(path: &Path) -> Result<bool, Error> {
     let mut cmd = git_cmd("check git is dirty")?;
     let output = cmd
@@ -3,6 +3,7 @@ use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
 
 use anyhow::{Context, Result};
 use clap::ValueEnum;
@@ -14,11 +15,13 @@ use tracing::error;
 use url::Url;
 
 use crate::config::{
-    self, Config, HookOptions, Language, LocalHook, MANIFEST_FILE, ManifestHook, MetaHook,
-    RemoteHook, Stage, read_manifest,
+    self, Config, FilePattern, HookOptions, Language, LocalHook, MANIFEST_FILE, ManifestHook,
+    MetaHook, RemoteHook, Stage, read_manifest,
 };
+use crate::diagnostic::{LocatedError, Location};
 use crate::languages::version::LanguageRequest;
-use crate::store::Store;
+use crate::store::{Store, relocate};
+use crate::version::check_minimum_versions;
 
 #[derive(Error, Debug)]
 pub(crate) enum Error {
@@ -110,11 +113,27 @@ pub(crate) struct HookBuilder {
     config: ManifestHook,
     // The index of the hook in the project configuration.
     idx: usize,
+    // The directory of the project, relative to the workspace root.
+    project_dir: PathBuf,
+    // The config file that defines the hook.
+    config_path: PathBuf,
 }
 
 impl HookBuilder {
-    pub(crate) fn new(repo: Arc<Repo>, config: ManifestHook, idx: usize) -> Self {
-        Self { repo, config, idx }
+    pub(crate) fn new(
+        repo: Arc<Repo>,
+        config: ManifestHook,
+        idx: usize,
+        project_dir: PathBuf,
+        config_path: PathBuf,
+    ) -> Self {
+        Self {
+            repo,
+            config,
+            idx,
+            project_dir,
+            config_path,
+        }
     }
 
     /// Update the hook from the project level hook configuration.
@@ -164,12 +183,23 @@ impl HookBuilder {
         options.pass_filenames.get_or_insert(true);
         options.require_serial.get_or_insert(false);
         options.verbose.get_or_insert(false);
+        options.stream.get_or_insert(false);
+        options.tags.get_or_insert_default();
         options
             .stages
-            .get_or_insert(Stage::value_variants().to_vec());
+            .get_or_insert_with(|| Stage::value_variants().to_vec().into());
         options.additional_dependencies.get_or_insert_default();
     }
 
+    /// An error pointing to the `key` of the hook in the config file.
+    fn located(&self, key: &'static str, label: String, message: String) -> anyhow::Error {
+        let location = Location::HookKey {
+            hook: self.config.id.clone(),
+            key,
+        };
+        LocatedError::new(&self.config_path, location, label, message).into()
+    }
+
     /// Check the hook configuration.
     fn check(&self) -> Result<(), Error> {
         let language = self.config.language;
@@ -183,13 +213,26 @@ impl HookBuilder {
             .as_ref()
             .map_or(&[][..], |deps| deps.as_slice());
 
+        check_minimum_versions(
+            self.config.options.minimum_pre_commit_version.as_deref(),
+            self.config.options.minimum_prek_version.as_deref(),
+        )
+        .map_err(|e| Error::InvalidHook {
+            hook: self.config.id.clone(),
+            error: self.located(e.key(), e.label().to_string(), e.to_string()),
+        })?;
+
         if !language.supports_dependency() && !additional_dependencies.is_empty() {
             return Err(Error::InvalidHook {
                 hook: self.config.id.clone(),
-                error: anyhow::anyhow!(
-                    "Hook specified `additional_dependencies` `{}` but the language `{}` does not support installing dependencies for now",
-                    additional_dependencies.join(", "),
-                    language,
+                error: self.located(
+                    "additional_dependencies",
+                    format!("`{language}` does not support installing dependencies"),
+                    format!(
+                        "Hook specified `additional_dependencies` `{}` but the language `{}` does not support installing dependencies for now",
+                        additional_dependencies.join(", "),
+                        language,
+                    ),
                 ),
             });
         }
@@ -200,10 +243,14 @@ impl HookBuilder {
             {
                 return Err(Error::InvalidHook {
                     hook: self.config.id.clone(),
-                    error: anyhow::anyhow!(
-                        "Hook specified `language_version` `{}` but the language `{}` does not install an environment",
-                        language_version,
-                        language,
+                    error: self.located(
+                        "language_version",
+                        format!("`{language}` does not install an environment"),
+                        format!(
+                            "Hook specified `language_version` `{}` but the language `{}` does not install an environment",
+                            language_version,
+                            language,
+                        ),
                     ),
                 });
             }
@@ -211,10 +258,14 @@ impl HookBuilder {
             if !additional_dependencies.is_empty() {
                 return Err(Error::InvalidHook {
                     hook: self.config.id.clone(),
-                    error: anyhow::anyhow!(
-                        "Hook specified `additional_dependencies` `{}` but the language `{}` does not install an environment",
-                        additional_dependencies.join(", "),
-                        language,
+                    error: self.located(
+                        "additional_dependencies",
+                        format!("`{language}` does not install an environment"),
+                        format!(
+                            "Hook specified `additional_dependencies` `{}` but the language `{}` does not install an environment",
+                            additional_dependencies.join(", "),
+                            language,
+                        ),
                     ),
                 });
             }
@@ -228,13 +279,22 @@ impl HookBuilder {
         self.check()?;
         self.fill_in_defaults();
 
-        let options = self.config.options;
-        let language_version = options.language_version.expect("language_version not set");
+        let language_version = self
+            .config
+            .options
+            .language_version
+            .take()
+            .expect("language_version not set");
         let language_request = LanguageRequest::parse(self.config.language, &language_version)
             .map_err(|e| Error::InvalidHook {
                 hook: self.config.id.clone(),
-                error: anyhow::anyhow!(e),
+                error: self.located(
+                    "language_version",
+                    "invalid version".to_string(),
+                    e.to_string(),
+                ),
             })?;
+        let options = self.config.options;
 
         let entry = Entry::new(self.config.id.clone(), self.config.entry);
 
@@ -250,6 +310,8 @@ impl HookBuilder {
             additional_dependencies,
             dependencies: OnceLock::new(),
             repo: self.repo,
+            project_dir: self.project_dir,
+            config_path: self.config_path,
             idx: self.idx,
             id: self.config.id,
             name: self.config.name,
@@ -267,8 +329,10 @@ impl HookBuilder {
             description: options.description,
             log_file: options.log_file,
             require_serial: options.require_serial.expect("require_serial not set"),
-            stages: options.stages.expect("stages not set"),
+            stages: options.stages.expect("stages not set").into(),
             verbose: options.verbose.expect("verbose not set"),
+            stream: options.stream.expect("stream not set"),
+            tags: options.tags.expect("tags not set"),
             minimum_pre_commit_version: options.minimum_pre_commit_version,
         })
     }
@@ -301,6 +365,10 @@ impl Entry {
 #[derive(Debug, Clone)]
 pub(crate) struct Hook {
     repo: Arc<Repo>,
+    // The directory of the project that defines the hook, relative to the workspace root.
+    project_dir: PathBuf,
+    // The config file that defines the hook.
+    config_path: PathBuf,
     // Cached computed dependencies.
     dependencies: OnceLock<FxHashSet<String>>,
 
@@ -311,8 +379,8 @@ pub(crate) struct Hook {
     pub entry: Entry,
     pub language: Language,
     pub alias: String,
-    pub files: Option<String>,
-    pub exclude: Option<String>,
+    pub files: Option<FilePattern>,
+    pub exclude: Option<FilePattern>,
     pub types: Vec<String>,
     pub types_or: Vec<String>,
     pub exclude_types: Vec<String>,
@@ -327,6 +395,8 @@ pub(crate) struct Hook {
     pub require_serial: bool,
     pub stages: Vec<Stage>,
     pub verbose: bool,
+    pub stream: bool,
+    pub tags: Vec<String>,
     pub minimum_pre_commit_version: Option<String>,
 }
 
@@ -350,6 +420,27 @@ impl Hook {
         self.repo.path()
     }
 
+    /// Get the directory of the project that defines the hook, relative to the workspace root.
+    ///
+    /// It's empty for the project at the workspace root.
+    pub(crate) fn project_dir(&self) -> &Path {
+        &self.project_dir
+    }
+
+    /// Get the config file that defines the hook.
+    pub(crate) fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// Get the directory to run the hook in, filenames passed to the hook are relative to it.
+    pub(crate) fn work_dir(&self) -> &Path {
+        if self.project_dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            &self.project_dir
+        }
+    }
+
     pub(crate) fn is_local(&self) -> bool {
         matches!(&*self.repo, Repo::Local { .. })
     }
@@ -423,20 +514,45 @@ impl InstalledHook {
         info.env_path.join(".prek-hook.json").is_file()
     }
 
-    /// Mark the hook as installed in the environment.
-    pub(crate) async fn mark_as_installed(&self, _store: &Store) -> Result<()> {
-        let Self::Installed { info, .. } = self else {
-            return Ok(());
+    /// Mark the hook as installed, moving its environment from the staging directory
+    /// into the store.
+    ///
+    /// The install info is written before the environment is renamed into place, so an
+    /// environment in the store is always complete.
+    pub(crate) async fn mark_as_installed(self, store: &Store) -> Result<Self> {
+        let Self::Installed { hook, info } = self else {
+            return Ok(self);
         };
 
-        let content = serde_json::to_string_pretty(info.as_ref())
-            .context("Failed to serialize install info")?;
+        let staging = info.env_path.clone();
+        let mut info = Arc::unwrap_or_clone(info);
+        let name = staging
+            .file_name()
+            .context("Environment path has no file name")?;
+        info.env_path = store.hooks_dir().join(name);
 
-        fs_err::tokio::write(info.env_path.join(".prek-hook.json"), content)
+        let content =
+            serde_json::to_string_pretty(&info).context("Failed to serialize install info")?;
+        fs_err::tokio::write(staging.join(".prek-hook.json"), content)
             .await
             .context("Failed to write install info")?;
 
-        Ok(())
+        // Installers may write the path of the environment into scripts and symlinks. Python
+        // venvs are created relocatable, so only their symlinks need it.
+        let (from, to) = (staging.clone(), info.env_path.clone());
+        let rewrite_files = hook.language != Language::Python;
+        tokio::task::spawn_blocking(move || relocate(&from, &from, &to, rewrite_files))
+            .await?
+            .context("Failed to relocate environment")?;
+
+        fs_err::tokio::rename(&staging, &info.env_path)
+            .await
+            .context("Failed to move environment into place")?;
+
+        Ok(Self::Installed {
+            hook,
+            info: Arc::new(info),
+        })
     }
 }
 
@@ -460,7 +576,13 @@ impl Hash for InstallInfo {
     }
 }
 
-// TODO: detect collision
+/// The directory under the hooks directory where environments are built before they are
+/// moved into place.
+pub(crate) const STAGING_DIR: &str = ".tmp";
+
+/// The file marking a hook environment as broken.
+pub(crate) const BROKEN_MARKER: &str = ".prek-broken";
+
 fn random_directory() -> String {
     rand::rng()
         .sample_iter(&rand::distr::Alphanumeric)
@@ -470,17 +592,39 @@ fn random_directory() -> String {
 }
 
 impl InstallInfo {
-    pub fn new(language: Language, dependencies: FxHashSet<String>, hooks_dir: &Path) -> Self {
-        let env = random_directory();
+    /// A new environment, built in the staging directory of `hooks_dir` under a name not
+    /// used by any other environment.
+    ///
+    /// The name is claimed by creating the environment directory, so concurrent installs never
+    /// pick the same one.
+    pub fn new(
+        language: Language,
+        dependencies: FxHashSet<String>,
+        hooks_dir: &Path,
+    ) -> std::io::Result<Self> {
+        let staging = hooks_dir.join(STAGING_DIR);
+        fs_err::create_dir_all(&staging)?;
+        let name = loop {
+            let name = format!("{}-{}", language.as_str(), random_directory());
+            match fs_err::create_dir(staging.join(&name)) {
+                // Installed environments keep their name, it may be in use there too.
+                Ok(()) if hooks_dir.join(&name).exists() => {
+                    fs_err::remove_dir(staging.join(&name))?;
+                }
+                Ok(()) => break name,
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(err) => return Err(err),
+            }
+        };
 
-        Self {
+        Ok(Self {
             language,
             dependencies,
-            env_path: hooks_dir.join(format!("{}-{env}", language.as_str())),
+            env_path: staging.join(name),
             language_version: semver::Version::new(0, 0, 0),
             toolchain: PathBuf::new(),
             extra: FxHashMap::default(),
-        }
+        })
     }
 
     pub fn with_language_version(&mut self, version: semver::Version) -> &mut Self {
@@ -502,6 +646,33 @@ impl InstallInfo {
         self.extra.get(key)
     }
 
+    /// Record that the environment was used, as the modification time of its install info.
+    pub fn mark_used(&self) -> std::io::Result<()> {
+        fs_err::File::options()
+            .write(true)
+            .open(self.env_path.join(".prek-hook.json"))?
+            .file()
+            .set_modified(SystemTime::now())
+    }
+
+    /// Mark the environment as broken, so it's no longer used and removed once no process
+    /// uses the store.
+    pub fn mark_broken(&self) -> std::io::Result<()> {
+        fs_err::write(self.env_path.join(BROKEN_MARKER), "")
+    }
+
+    /// Whether the environment was marked as broken.
+    pub fn is_broken(&self) -> bool {
+        self.env_path.join(BROKEN_MARKER).exists()
+    }
+
+    /// When the environment was last installed or used.
+    pub fn last_used(&self) -> Option<SystemTime> {
+        fs_err::metadata(self.env_path.join(".prek-hook.json"))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
     pub fn matches(&self, hook: &Hook) -> bool {
         self.language == hook.language
             && self.dependencies.is_superset(hook.dependencies())
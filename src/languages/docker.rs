@@ -13,7 +13,7 @@ use crate::fs::CWD;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{run_by_batch, run_hook_cmd};
 use crate::store::Store;
 
 const PRE_COMMIT_LABEL: &str = "PRE_COMMIT";
@@ -136,7 +136,7 @@ impl Docker {
         Ok(Cow::Borrowed(path))
     }
 
-    pub(crate) async fn docker_run_cmd() -> Result<Cmd> {
+    pub(crate) async fn docker_run_cmd(hook: &Hook) -> Result<Cmd> {
         let mut command = Cmd::new("docker", "run container");
         command.arg("run").arg("--rm");
 
@@ -156,8 +156,16 @@ impl Docker {
             }));
         }
 
-        let cwd = &CWD.to_string_lossy();
-        let work_dir = Self::get_docker_path(cwd).await?;
+        // Mount the project directory of the hook.
+        let cwd = if hook.project_dir().as_os_str().is_empty() {
+            CWD.to_string_lossy()
+        } else {
+            CWD.join(hook.project_dir())
+                .to_string_lossy()
+                .to_string()
+                .into()
+        };
+        let work_dir = Self::get_docker_path(&cwd).await?;
         command
             .arg("-v")
             // https://docs.docker.com/engine/reference/commandline/run/#mount-volumes-from-container-volumes-from
@@ -177,7 +185,7 @@ impl LanguageImpl for Docker {
             hook.language,
             hook.dependencies().clone(),
             &store.hooks_dir(),
-        );
+        )?;
         let installed_hook = InstalledHook::Installed {
             hook,
             info: Arc::new(info),
@@ -197,8 +205,12 @@ impl LanguageImpl for Docker {
         Ok(installed_hook)
     }
 
-    async fn check_health(&self) -> Result<()> {
-        todo!()
+    async fn check_health(&self, _hook: &Hook, info: &InstallInfo) -> Result<()> {
+        // The image is built again if it's missing when the hook runs.
+        if !info.env_path.is_dir() {
+            anyhow::bail!("Environment `{}` does not exist", info.env_path.display());
+        }
+        Ok(())
     }
 
     async fn run(
@@ -216,20 +228,15 @@ impl LanguageImpl for Docker {
 
         let run = async move |batch: Vec<String>| {
             // docker run [OPTIONS] IMAGE [COMMAND] [ARG...]
-            let mut cmd = Docker::docker_run_cmd().await?;
-            let cmd = cmd
-                .arg("--entrypoint")
+            let mut cmd = Docker::docker_run_cmd(hook).await?;
+            cmd.arg("--entrypoint")
                 .arg(&entry[0])
                 .arg(&docker_tag)
                 .args(&entry[1..])
                 .args(&hook.args)
-                .args(batch)
-                .check(false);
+                .args(batch);
 
-            let mut output = cmd.output().await?;
-            output.stdout.extend(output.stderr);
-            let code = output.status.code().unwrap_or(1);
-            anyhow::Ok((code, output.stdout))
+            run_hook_cmd(hook, &mut cmd).await
         };
 
         let results = run_by_batch(hook, filenames, run).await?;
@@ -2,10 +2,10 @@ use std::sync::Arc;
 
 use anyhow::Result;
 
-use crate::hook::{Hook, InstalledHook};
+use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::languages::docker::Docker;
-use crate::run::run_by_batch;
+use crate::run::{run_by_batch, run_hook_cmd};
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -16,8 +16,8 @@ impl LanguageImpl for DockerImage {
         Ok(InstalledHook::NoNeedInstall(hook))
     }
 
-    async fn check_health(&self) -> Result<()> {
-        todo!()
+    async fn check_health(&self, _hook: &Hook, _info: &InstallInfo) -> Result<()> {
+        Ok(())
     }
 
     async fn run(
@@ -28,17 +28,10 @@ impl LanguageImpl for DockerImage {
     ) -> Result<(i32, Vec<u8>)> {
         let entry = hook.entry.parsed()?;
         let run = async move |batch: Vec<String>| {
-            let mut cmd = Docker::docker_run_cmd().await?;
-            let cmd = cmd
-                .args(&entry[..])
-                .args(&hook.args)
-                .args(batch)
-                .check(false);
-
-            let mut output = cmd.output().await?;
-            output.stdout.extend(output.stderr);
-            let code = output.status.code().unwrap_or(1);
-            anyhow::Ok((code, output.stdout))
+            let mut cmd = Docker::docker_run_cmd(hook).await?;
+            cmd.args(&entry[..]).args(&hook.args).args(batch);
+
+            run_hook_cmd(hook, &mut cmd).await
         };
 
         let results = run_by_batch(hook, filenames, run).await?;
@@ -7,12 +7,13 @@ use anyhow::Context;
 use constants::env_vars::EnvVars;
 
 use crate::hook::{Hook, InstallInfo, InstalledHook};
-use crate::languages::LanguageImpl;
 use crate::languages::golang::GoRequest;
 use crate::languages::golang::installer::GoInstaller;
 use crate::languages::version::LanguageRequest;
+use crate::languages::{LanguageImpl, check_entry, check_exists};
+use crate::offline;
 use crate::process::Cmd;
-use crate::run::{prepend_paths, run_by_batch};
+use crate::run::{prepend_paths, run_by_batch, run_hook_cmd};
 use crate::store::{CacheBucket, Store};
 
 #[derive(Debug, Copy, Clone)]
@@ -35,7 +36,7 @@ impl LanguageImpl for Golang {
             hook.language,
             hook.dependencies().clone(),
             &store.hooks_dir(),
-        );
+        )?;
         info.with_toolchain(go.bin().to_path_buf())
             .with_language_version(go.version().deref().clone());
 
@@ -54,31 +55,43 @@ impl LanguageImpl for Golang {
             .and_then(|p| p.parent())
             .expect("Go root should exist");
         let go_cache = store.cache_path(CacheBucket::Go);
-        // GOPATH used to store downloaded source code (in $GOPATH/pkg/mod)
-        if let Some(repo) = hook.repo_path() {
-            go.cmd("go install")
-                .arg("install")
-                .arg("./...")
+        let go_install = || {
+            let mut cmd = go.cmd("go install");
+            cmd.arg("install")
                 .env(EnvVars::GOTOOLCHAIN, "local")
                 .env(EnvVars::GOROOT, go_root)
                 .env(EnvVars::GOBIN, bin_dir(&info.env_path))
-                .env(EnvVars::GOPATH, &go_cache)
+                // GOPATH used to store downloaded source code (in $GOPATH/pkg/mod)
+                .env(EnvVars::GOPATH, &go_cache);
+            if offline::is_enabled() {
+                // Only use the modules already in the module cache.
+                cmd.env(EnvVars::GOFLAGS, "-mod=mod")
+                    .env(EnvVars::GOPROXY, "off");
+            }
+            cmd
+        };
+        if let Some(repo) = hook.repo_path() {
+            go_install()
+                .arg("./...")
                 .current_dir(repo)
                 .check(true)
                 .output()
-                .await?;
+                .await
+                .map_err(|err| {
+                    offline::or_missing(err, || {
+                        offline::Missing::new(format!("dependencies of repo `{}`", hook.repo()))
+                    })
+                })?;
         }
         for dep in &hook.additional_dependencies {
-            go.cmd("go install")
-                .arg("install")
+            go_install()
                 .arg(dep)
-                .env(EnvVars::GOTOOLCHAIN, "local")
-                .env(EnvVars::GOROOT, go_root)
-                .env(EnvVars::GOBIN, bin_dir(&info.env_path))
-                .env(EnvVars::GOPATH, &go_cache)
                 .check(true)
                 .output()
-                .await?;
+                .await
+                .map_err(|err| {
+                    offline::or_missing(err, || offline::Missing::new(format!("Go module `{dep}`")))
+                })?;
         }
 
         Ok(InstalledHook::Installed {
@@ -87,8 +100,11 @@ impl LanguageImpl for Golang {
         })
     }
 
-    async fn check_health(&self) -> anyhow::Result<()> {
-        todo!()
+    async fn check_health(&self, hook: &Hook, info: &InstallInfo) -> anyhow::Result<()> {
+        // Hooks run with the toolchain as `GOROOT`.
+        check_exists(&info.toolchain)?;
+        let go_root_bin = info.toolchain.parent().expect("Go root should exist");
+        check_entry(hook, &[&bin_dir(&info.env_path), go_root_bin])
     }
 
     async fn run(
@@ -110,22 +126,17 @@ impl LanguageImpl for Golang {
 
         let entry = hook.entry.parsed()?;
         let run = async move |batch: Vec<String>| {
-            let mut output = Cmd::new(&entry[0], "go hook")
-                .args(&entry[1..])
+            let mut cmd = Cmd::new(&entry[0], "go hook");
+            cmd.args(&entry[1..])
                 .env("PATH", &new_path)
                 .env(EnvVars::GOTOOLCHAIN, "local")
                 .env(EnvVars::GOROOT, go_root)
                 .env(EnvVars::GOBIN, &go_bin)
                 .env(EnvVars::GOPATH, &go_cache)
                 .args(&hook.args)
-                .args(batch)
-                .check(false)
-                .output()
-                .await?;
+                .args(batch);
 
-            output.stdout.extend(output.stderr);
-            let code = output.status.code().unwrap_or(1);
-            anyhow::Ok((code, output.stdout))
+            run_hook_cmd(hook, &mut cmd).await
         };
 
         let results = run_by_batch(hook, filenames, run).await?;
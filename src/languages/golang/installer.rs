@@ -6,16 +6,19 @@ use std::str::FromStr;
 use anyhow::{Context, Result};
 use itertools::Itertools;
 use reqwest::Client;
+use serde::Deserialize;
 use target_lexicon::{Architecture, HOST, OperatingSystem};
+use tokio::sync::OnceCell;
 use tracing::{debug, trace, warn};
 
 use crate::fs::LockedFile;
-use crate::git;
 use crate::languages::download_and_extract;
 use crate::languages::golang::GoRequest;
 use crate::languages::golang::golang::bin_dir;
 use crate::languages::golang::version::GoVersion;
+use crate::offline;
 use crate::process::Cmd;
+use crate::settings::Settings;
 
 pub(crate) struct GoResult {
     path: PathBuf,
@@ -87,6 +90,36 @@ impl GoResult {
 pub(crate) struct GoInstaller {
     root: PathBuf,
     client: Client,
+    releases: OnceCell<Vec<GoRelease>>,
+}
+
+/// The Go download url, or the mirror from the global settings.
+fn download_url() -> String {
+    Settings::get()
+        .mirrors
+        .go()
+        .unwrap_or_else(|| "https://go.dev/dl".to_string())
+}
+
+/// The release feed of the Go download url, which lists every release with the sha256
+/// checksum of its files.
+///
+/// The feed is read from the mirror too, a mirror is trusted like go.dev itself.
+fn release_feed_url() -> String {
+    format!("{}/?mode=json&include=all", download_url())
+}
+
+#[derive(Debug, Deserialize)]
+struct GoRelease {
+    /// The release version, e.g. `go1.24.5`.
+    version: String,
+    files: Vec<GoReleaseFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoReleaseFile {
+    filename: String,
+    sha256: String,
 }
 
 impl GoInstaller {
@@ -94,6 +127,7 @@ impl GoInstaller {
         Self {
             root,
             client: Client::new(),
+            releases: OnceCell::new(),
         }
     }
 
@@ -112,6 +146,10 @@ impl GoInstaller {
             return Ok(go);
         }
 
+        if offline::is_enabled() {
+            return Err(offline::Missing::new(format!("Go {request}")).into());
+        }
+
         let resolved_version = self
             .resolve_version(request)
             .await
@@ -156,26 +194,15 @@ impl GoInstaller {
     }
 
     async fn resolve_version(&self, req: &GoRequest) -> Result<GoVersion> {
-        let output = git::git_cmd("list go tags")?
-            .arg("ls-remote")
-            .arg("--tags")
-            .arg("https://github.com/golang/go")
-            .output()
+        let version = self
+            .releases()
             .await?
-            .stdout;
-        let output_str = String::from_utf8(output)?;
-        let versions: Vec<GoVersion> = output_str
-            .lines()
-            .filter_map(|line| {
-                let tag = line.split('\t').nth(1)?;
-                let tag = tag.strip_prefix("refs/tags/go")?;
-                GoVersion::from_str(tag).ok()
+            .iter()
+            .filter_map(|release| {
+                let version = release.version.strip_prefix("go")?;
+                GoVersion::from_str(version).ok()
             })
             .sorted_unstable_by(|a, b| b.cmp(a))
-            .collect();
-
-        let version = versions
-            .into_iter()
             .find(|version| req.matches(version, None))
             .context("Version not found on remote")?;
         Ok(version)
@@ -206,16 +233,48 @@ impl GoInstaller {
 
         let ext = if cfg!(windows) { "zip" } else { "tar.gz" };
         let filename = format!("go{version}.{os}-{arch}.{ext}");
-        let url = format!("https://go.dev/dl/{filename}");
+        let url = format!("{}/{filename}", download_url());
         let target = self.root.join(version.to_string());
+        let sha256 = self.checksum(&filename).await?;
 
-        download_and_extract(&self.client, &url, &target, &filename, &self.root)
+        download_and_extract(&self.client, &url, &target, &filename, &sha256, &self.root)
             .await
             .context("Failed to download and extract Go")?;
 
         Ok(GoResult::from_dir(&target).with_version(version.clone()))
     }
 
+    /// The releases in the release feed, downloaded once.
+    async fn releases(&self) -> Result<&[GoRelease]> {
+        let releases = self
+            .releases
+            .get_or_try_init(async || {
+                let url = release_feed_url();
+                self.client
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .with_context(|| format!("Failed to download the Go release feed from {url}"))?
+                    .json::<Vec<GoRelease>>()
+                    .await
+                    .context("Failed to parse the Go release feed")
+            })
+            .await?;
+        Ok(releases)
+    }
+
+    /// Get the sha256 checksum of a release file from the release feed.
+    async fn checksum(&self, filename: &str) -> Result<String> {
+        self.releases()
+            .await?
+            .iter()
+            .flat_map(|release| &release.files)
+            .find(|file| file.filename == filename)
+            .map(|file| file.sha256.clone())
+            .with_context(|| format!("No checksum found for `{filename}` in the Go release feed"))
+    }
+
     async fn find_system_go(&self, go_request: &GoRequest) -> Result<Option<GoResult>> {
         let go_paths = match which::which_all("go") {
             Ok(paths) => paths,
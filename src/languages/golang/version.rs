@@ -114,6 +114,19 @@ impl FromStr for GoRequest {
     }
 }
 
+impl Display for GoRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Any => write!(f, "any version"),
+            Self::Major(major) => write!(f, "{major}"),
+            Self::MajorMinor(major, minor) => write!(f, "{major}.{minor}"),
+            Self::MajorMinorPatch(major, minor, patch) => write!(f, "{major}.{minor}.{patch}"),
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Range(_, raw) => write!(f, "{raw}"),
+        }
+    }
+}
+
 impl GoRequest {
     fn parse_version_numbers(
         version_str: &str,
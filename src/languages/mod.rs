@@ -1,16 +1,20 @@
-use std::path::Path;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use futures::TryStreamExt;
-use tokio_util::compat::FuturesAsyncReadCompatExt;
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+use tokio::io::AsyncWriteExt;
 use tracing::trace;
 
 use crate::archive::ArchiveExtension;
 use crate::config::Language;
-use crate::hook::{Hook, InstalledHook};
+use crate::hook::{Hook, InstallInfo, InstalledHook};
+use crate::run::prepend_paths;
 use crate::store::Store;
-use crate::{archive, builtin};
+use crate::{archive, builtin, run};
 
 mod docker;
 mod docker_image;
@@ -34,7 +38,11 @@ static UNIMPLEMENTED: Unimplemented = Unimplemented;
 
 trait LanguageImpl {
     async fn install(&self, hook: Arc<Hook>, store: &Store) -> Result<InstalledHook>;
-    async fn check_health(&self) -> Result<()>;
+    /// Check that an installed environment still works for the hook, e.g. that the toolchain it
+    /// was built from still exists.
+    ///
+    /// Fails with [`EntryNotFound`] if only the entry of the hook isn't found.
+    async fn check_health(&self, hook: &Hook, info: &InstallInfo) -> Result<()>;
     async fn run(
         &self,
         hook: &InstalledHook,
@@ -47,6 +55,14 @@ trait LanguageImpl {
 #[error("Language `{0}` is not implemented yet")]
 struct UnimplementedError(String);
 
+/// The entry of a hook is not found in its environment.
+///
+/// The entry may be a path relative to the hook's working directory that doesn't exist yet,
+/// so this doesn't make the environment broken.
+#[derive(thiserror::Error, Debug)]
+#[error("Entry `{0}` not found")]
+pub(crate) struct EntryNotFound(String);
+
 struct Unimplemented;
 
 impl LanguageImpl for Unimplemented {
@@ -54,7 +70,7 @@ impl LanguageImpl for Unimplemented {
         Ok(InstalledHook::NoNeedInstall(hook))
     }
 
-    async fn check_health(&self) -> Result<()> {
+    async fn check_health(&self, _hook: &Hook, _info: &InstallInfo) -> Result<()> {
         Ok(())
     }
 
@@ -154,17 +170,17 @@ impl Language {
         }
     }
 
-    pub async fn check_health(&self) -> Result<()> {
+    pub async fn check_health(&self, hook: &Hook, info: &InstallInfo) -> Result<()> {
         match self {
-            Self::Golang => GOLANG.check_health().await,
-            Self::Python => PYTHON.check_health().await,
-            Self::Node => NODE.check_health().await,
-            Self::System => SYSTEM.check_health().await,
-            Self::Fail => FAIL.check_health().await,
-            Self::Docker => DOCKER.check_health().await,
-            Self::DockerImage => DOCKER_IMAGE.check_health().await,
-            Self::Script => SCRIPT.check_health().await,
-            _ => UNIMPLEMENTED.check_health().await,
+            Self::Golang => GOLANG.check_health(hook, info).await,
+            Self::Python => PYTHON.check_health(hook, info).await,
+            Self::Node => NODE.check_health(hook, info).await,
+            Self::System => SYSTEM.check_health(hook, info).await,
+            Self::Fail => FAIL.check_health(hook, info).await,
+            Self::Docker => DOCKER.check_health(hook, info).await,
+            Self::DockerImage => DOCKER_IMAGE.check_health(hook, info).await,
+            Self::Script => SCRIPT.check_health(hook, info).await,
+            _ => UNIMPLEMENTED.check_health(hook, info).await,
         }
     }
 
@@ -176,7 +192,11 @@ impl Language {
     ) -> Result<(i32, Vec<u8>)> {
         // fast path for hooks implemented in Rust
         if builtin::check_fast_path(hook) {
-            return builtin::run_fast_path(hook, filenames).await;
+            let (status, output) = builtin::run_fast_path(hook, filenames).await?;
+            if hook.stream {
+                run::write_streamed(hook, &output)?;
+            }
+            return Ok((status, output));
         }
 
         match self {
@@ -269,8 +289,41 @@ async fn download_and_extract(
     url: &str,
     target: &Path,
     filename: &str,
+    sha256: &str,
     scratch: &Path,
 ) -> Result<()> {
+    let temp_dir = download_and_unpack(client, url, filename, sha256, scratch).await?;
+
+    let extracted = match archive::strip_component(temp_dir.path()) {
+        Ok(top_level) => top_level,
+        Err(archive::Error::NonSingularArchive(_)) => temp_dir.keep(),
+        Err(err) => return Err(err.into()),
+    };
+
+    if target.is_dir() {
+        trace!(target = %target.display(), "Removing existing target");
+        fs_err::tokio::remove_dir_all(&target).await?;
+    }
+
+    trace!(temp_dir = ?extracted, target = %target.display(), "Moving to target");
+    // TODO: retry on Windows
+    fs_err::tokio::rename(extracted, target).await?;
+
+    Ok(())
+}
+
+/// Download an archive, verify it against the expected sha256 checksum, and unpack it
+/// into a temporary directory under `scratch`.
+///
+/// The archive is only unpacked after it has been verified, nothing is left behind
+/// in `scratch` if the download or the verification fails.
+async fn download_and_unpack(
+    client: &reqwest::Client,
+    url: &str,
+    filename: &str,
+    sha256: &str,
+    scratch: &Path,
+) -> Result<TempDir> {
     let response = client
         .get(url)
         .send()
@@ -284,32 +337,128 @@ async fn download_and_extract(
         );
     }
 
-    let tarball = response
-        .bytes_stream()
-        .map_err(std::io::Error::other)
-        .into_async_read()
-        .compat();
+    let archive_file = tempfile::NamedTempFile::new_in(scratch)?;
+    trace!(url = %url, file = ?archive_file.path(), "Downloading");
 
-    let temp_dir = tempfile::tempdir_in(scratch)?;
-    trace!(url = %url, temp_dir = ?temp_dir.path(), "Downloading");
+    let mut file = fs_err::tokio::File::create(archive_file.path()).await?;
+    let mut hasher = Sha256::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream
+        .try_next()
+        .await
+        .with_context(|| format!("Failed to download file from {url}"))?
+    {
+        hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    drop(file);
+
+    let actual = hex::encode(hasher.finalize());
+    if !actual.eq_ignore_ascii_case(sha256) {
+        anyhow::bail!(
+            "Checksum mismatch for `{filename}` downloaded from {url}: expected sha256 `{sha256}`, got `{actual}`"
+        );
+    }
+    trace!(%filename, %actual, "Verified checksum");
 
+    let temp_dir = tempfile::tempdir_in(scratch)?;
     let ext = ArchiveExtension::from_path(filename)?;
-    archive::unpack(tarball, ext, temp_dir.path()).await?;
+    let reader = fs_err::tokio::File::open(archive_file.path()).await?;
+    archive::unpack(reader, ext, temp_dir.path()).await?;
 
-    let extracted = match archive::strip_component(temp_dir.path()) {
-        Ok(top_level) => top_level,
-        Err(archive::Error::NonSingularArchive(_)) => temp_dir.keep(),
-        Err(err) => return Err(err.into()),
-    };
+    Ok(temp_dir)
+}
 
-    if target.is_dir() {
-        trace!(target = %target.display(), "Removing existing target");
-        fs_err::tokio::remove_dir_all(&target).await?;
-    }
+/// Find the checksum of `filename` in a checksum file in the `sha256sum` format,
+/// like `SHASUMS256.txt` of Node.js releases.
+fn find_checksum<'a>(checksums: &'a str, filename: &str) -> Option<&'a str> {
+    checksums.lines().find_map(|line| {
+        let (checksum, name) = line.trim().split_once(char::is_whitespace)?;
+        // `*` marks binary mode.
+        let name = name.trim_start().trim_start_matches('*');
+        (name == filename).then_some(checksum)
+    })
+}
 
-    trace!(temp_dir = ?extracted, target = %target.display(), "Moving to target");
-    // TODO: retry on Windows
-    fs_err::tokio::rename(extracted, target).await?;
+/// Check that a file the environment needs exists, following symlinks.
+fn check_exists(path: &Path) -> Result<()> {
+    if !path.exists() {
+        anyhow::bail!("`{}` does not exist", path.display());
+    }
+    Ok(())
+}
 
+/// Check that the entry executable of a hook is found with the environment's `bin_dirs`
+/// first in `PATH`, and that the interpreter in its shebang, if any, exists.
+///
+/// Fails with [`EntryNotFound`] if the entry isn't found.
+fn check_entry(hook: &Hook, bin_dirs: &[&Path]) -> Result<()> {
+    let entry = hook.entry.parsed()?;
+    let path = prepend_paths(bin_dirs).context("Failed to join PATH")?;
+    let executable = which::which_in(&entry[0], Some(path), hook.work_dir())
+        .map_err(|_| EntryNotFound(entry[0].clone()))?;
+
+    let mut line = Vec::new();
+    fs_err::File::open(&executable)
+        .map(|file| BufReader::new(file).take(4096))
+        .and_then(|mut reader| reader.read_until(b'\n', &mut line))
+        .with_context(|| format!("Failed to read `{}`", executable.display()))?;
+    if let Some(interpreter) = shebang_interpreter(&line) {
+        if !interpreter.exists() {
+            anyhow::bail!(
+                "Interpreter `{}` of `{}` does not exist",
+                interpreter.display(),
+                executable.display()
+            );
+        }
+    }
     Ok(())
 }
+
+/// The interpreter in the shebang line of a script.
+fn shebang_interpreter(line: &[u8]) -> Option<PathBuf> {
+    let line = line.strip_prefix(b"#!")?;
+    let line = std::str::from_utf8(line).ok()?;
+    line.split_whitespace().next().map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{find_checksum, shebang_interpreter};
+
+    #[test]
+    fn shebang() {
+        assert_eq!(
+            shebang_interpreter(b"#!/home/user/.cache/prek/hooks/python-abc/bin/python\n"),
+            Some(PathBuf::from(
+                "/home/user/.cache/prek/hooks/python-abc/bin/python"
+            ))
+        );
+        assert_eq!(
+            shebang_interpreter(b"#! /usr/bin/env node\n"),
+            Some(PathBuf::from("/usr/bin/env"))
+        );
+        assert_eq!(shebang_interpreter(b"\x7fELF\x02\x01"), None);
+    }
+
+    #[test]
+    fn checksums() {
+        let checksums = "\
+            0a1b  node-v22.0.0-darwin-arm64.tar.xz
+            2c3d  node-v22.0.0-linux-x64.tar.xz
+            4e5f *uv-x86_64-unknown-linux-gnu.tar.gz
+        ";
+        assert_eq!(
+            find_checksum(checksums, "node-v22.0.0-linux-x64.tar.xz"),
+            Some("2c3d")
+        );
+        assert_eq!(
+            find_checksum(checksums, "uv-x86_64-unknown-linux-gnu.tar.gz"),
+            Some("4e5f")
+        );
+        assert_eq!(find_checksum(checksums, "node-v22.0.0-linux-x64.tar"), None);
+    }
+}
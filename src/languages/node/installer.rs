@@ -11,10 +11,12 @@ use target_lexicon::{Architecture, HOST, OperatingSystem};
 use tracing::{debug, trace, warn};
 
 use crate::fs::LockedFile;
-use crate::languages::download_and_extract;
 use crate::languages::node::NodeRequest;
 use crate::languages::node::version::NodeVersion;
+use crate::languages::{download_and_extract, find_checksum};
+use crate::offline;
 use crate::process::Cmd;
+use crate::settings::Settings;
 
 #[derive(Debug)]
 pub(crate) struct NodeResult {
@@ -87,6 +89,14 @@ pub(crate) struct NodeInstaller {
     client: Client,
 }
 
+/// The Node.js distribution url, or the mirror from the global settings.
+fn dist_url() -> String {
+    Settings::get()
+        .mirrors
+        .node()
+        .unwrap_or_else(|| "https://nodejs.org/dist".to_string())
+}
+
 impl NodeInstaller {
     pub(crate) fn new(root: PathBuf) -> Self {
         Self {
@@ -112,6 +122,10 @@ impl NodeInstaller {
             return Ok(node_result);
         }
 
+        if offline::is_enabled() {
+            return Err(offline::Missing::new(format!("Node.js {request}")).into());
+        }
+
         let resolved_version = self.resolve_version(request).await?;
         trace!(version = %resolved_version, "Installing node");
 
@@ -165,12 +179,11 @@ impl NodeInstaller {
 
     /// List all versions of Node.js available on the Node.js website.
     async fn list_remote_versions(&self) -> Result<Vec<NodeVersion>> {
-        let url = "https://nodejs.org/dist/index.json";
+        let url = format!("{}/index.json", dist_url());
         let versions: Vec<NodeVersion> = self.client.get(url).send().await?.json().await?;
         Ok(versions)
     }
 
-    // TODO: support mirror?
     /// Install a specific version of Node.js.
     async fn download(&self, version: &NodeVersion) -> Result<NodeResult> {
         let mut arch = match HOST.architecture {
@@ -197,16 +210,37 @@ impl NodeInstaller {
         let ext = if cfg!(windows) { "zip" } else { "tar.xz" };
 
         let filename = format!("node-v{}-{os}-{arch}.{ext}", version.version());
-        let url = format!("https://nodejs.org/dist/v{}/{filename}", version.version());
+        let url = format!("{}/v{}/{filename}", dist_url(), version.version());
         let target = self.root.join(version.to_string());
+        let sha256 = self.checksum(version, &filename).await?;
 
-        download_and_extract(&self.client, &url, &target, &filename, &self.root)
+        download_and_extract(&self.client, &url, &target, &filename, &sha256, &self.root)
             .await
             .context("Failed to download and extract Node.js")?;
 
         Ok(NodeResult::from_dir(&target).with_version(version.clone()))
     }
 
+    /// Get the sha256 checksum of a release file from the `SHASUMS256.txt` of the release.
+    ///
+    /// The checksums are read from the mirror too, a mirror is trusted like nodejs.org itself:
+    /// they catch corrupted downloads, not a compromised mirror.
+    async fn checksum(&self, version: &NodeVersion, filename: &str) -> Result<String> {
+        let url = format!("{}/v{}/SHASUMS256.txt", dist_url(), version.version());
+        let checksums = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("Failed to download checksums from {url}"))?
+            .text()
+            .await?;
+        let checksum = find_checksum(&checksums, filename)
+            .with_context(|| format!("No checksum found for `{filename}` in {url}"))?;
+        Ok(checksum.to_string())
+    }
+
     /// Find a suitable system Node.js installation that matches the request.
     async fn find_system_node(&self, node_request: &NodeRequest) -> Result<Option<NodeResult>> {
         let node_paths = match which::which_all("node") {
@@ -14,9 +14,10 @@ use crate::languages::node::NodeRequest;
 use crate::languages::node::installer::{NodeInstaller, bin_dir, lib_dir};
 use crate::languages::node::version::EXTRA_KEY_LTS;
 use crate::languages::version::LanguageRequest;
-use crate::languages::{LanguageImpl, create_symlink_or_copy};
+use crate::languages::{LanguageImpl, check_entry, check_exists, create_symlink_or_copy};
+use crate::offline;
 use crate::process::Cmd;
-use crate::run::{prepend_paths, run_by_batch};
+use crate::run::{prepend_paths, run_by_batch, run_hook_cmd};
 use crate::store::{Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
@@ -46,7 +47,7 @@ impl LanguageImpl for Node {
             hook.language,
             hook.dependencies().clone(),
             &store.hooks_dir(),
-        );
+        )?;
 
         let lts = serde_json::to_string(&node.version().lts).context("Failed to serialize LTS")?;
         info.with_toolchain(node.node().to_path_buf());
@@ -92,22 +93,31 @@ impl LanguageImpl for Node {
             // so that `npm` can find `node`.
             let new_path = prepend_paths(&[&bin_dir]).context("Failed to join PATH")?;
 
-            Cmd::new(node.npm(), "npm install")
-                .arg("install")
+            let mut cmd = Cmd::new(node.npm(), "npm install");
+            cmd.arg("install")
                 .arg("-g")
                 .arg("--no-progress")
                 .arg("--no-save")
                 .arg("--no-fund")
                 .arg("--no-audit")
-                .arg("--install-links")
-                .args(&*deps)
+                .arg("--install-links");
+            if offline::is_enabled() {
+                // Only install packages from the npm cache.
+                cmd.arg("--offline");
+            }
+            cmd.args(&*deps)
                 .env("PATH", new_path)
                 .env(EnvVars::NPM_CONFIG_PREFIX, &info.env_path)
                 .env_remove(EnvVars::NPM_CONFIG_USERCONFIG)
                 .env(EnvVars::NODE_PATH, &lib_dir)
                 .check(true)
                 .output()
-                .await?;
+                .await
+                .map_err(|err| {
+                    offline::or_missing(err, || {
+                        offline::Missing::dependencies(&hook, "npm package")
+                    })
+                })?;
         }
 
         Ok(InstalledHook::Installed {
@@ -116,8 +126,11 @@ impl LanguageImpl for Node {
         })
     }
 
-    async fn check_health(&self) -> Result<()> {
-        todo!()
+    async fn check_health(&self, hook: &Hook, info: &InstallInfo) -> Result<()> {
+        let bin_dir = bin_dir(&info.env_path);
+        // The `node` in the environment links to the toolchain.
+        check_exists(&bin_dir.join("node").with_extension(EXE_EXTENSION))?;
+        check_entry(hook, &[&bin_dir])
     }
 
     async fn run(
@@ -145,21 +158,15 @@ impl LanguageImpl for Node {
                 Cmd::new(&entry[0], "node hook")
             };
 
-            let mut output = cmd
-                .args(&entry[1..])
+            cmd.args(&entry[1..])
                 .env("PATH", &new_path)
                 .env(EnvVars::NPM_CONFIG_PREFIX, env_dir)
                 .env_remove(EnvVars::NPM_CONFIG_USERCONFIG)
                 .env(EnvVars::NODE_PATH, lib_dir(env_dir))
                 .args(&hook.args)
-                .args(batch)
-                .check(false)
-                .output()
-                .await?;
+                .args(batch);
 
-            output.stdout.extend(output.stderr);
-            let code = output.status.code().unwrap_or(1);
-            anyhow::Ok((code, output.stdout))
+            run_hook_cmd(hook, &mut cmd).await
         };
 
         let results = run_by_batch(hook, filenames, run).await?;
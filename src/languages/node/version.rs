@@ -187,6 +187,20 @@ impl FromStr for NodeRequest {
     }
 }
 
+impl Display for NodeRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Any => write!(f, "any version"),
+            Self::Major(major) => write!(f, "{major}"),
+            Self::MajorMinor(major, minor) => write!(f, "{major}.{minor}"),
+            Self::MajorMinorPatch(major, minor, patch) => write!(f, "{major}.{minor}.{patch}"),
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Range(req) => write!(f, "{req}"),
+            Self::CodeName(code_name) => write!(f, "lts/{code_name}"),
+        }
+    }
+}
+
 pub(crate) const EXTRA_KEY_LTS: &str = "lts";
 
 impl NodeRequest {
@@ -247,7 +261,7 @@ mod tests {
     use crate::config::Language;
     use crate::hook::InstallInfo;
     use std::collections::HashSet;
-    use std::path::{Path, PathBuf};
+    use std::path::PathBuf;
     use std::str::FromStr;
 
     #[test]
@@ -296,8 +310,10 @@ mod tests {
     }
 
     #[test]
-    fn test_node_request_satisfied_by() {
-        let mut install_info = InstallInfo::new(Language::Node, HashSet::default(), Path::new("."));
+    fn test_node_request_satisfied_by() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let mut install_info =
+            InstallInfo::new(Language::Node, HashSet::default(), temp_dir.path())?;
         install_info
             .with_language_version(semver::Version::new(12, 18, 3))
             .with_toolchain(PathBuf::from("/usr/bin/node"))
@@ -332,5 +348,7 @@ mod tests {
 
         let request = NodeRequest::Range(semver::VersionReq::parse(">=13.0").unwrap());
         assert!(!request.satisfied_by(&install_info));
+
+        Ok(())
     }
 }
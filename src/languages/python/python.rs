@@ -9,13 +9,15 @@ use constants::env_vars::EnvVars;
 
 use crate::hook::InstalledHook;
 use crate::hook::{Hook, InstallInfo};
-use crate::languages::LanguageImpl;
 use crate::languages::python::PythonRequest;
 use crate::languages::python::uv::Uv;
 use crate::languages::version::LanguageRequest;
+use crate::languages::{LanguageImpl, check_entry, check_exists};
+use crate::offline;
 use crate::process;
 use crate::process::Cmd;
-use crate::run::{prepend_paths, run_by_batch};
+use crate::run::{prepend_paths, run_by_batch, run_hook_cmd};
+use crate::settings::Settings;
 use crate::store::{Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
@@ -53,7 +55,7 @@ impl LanguageImpl for Python {
             hook.language,
             hook.dependencies().clone(),
             &store.hooks_dir(),
-        );
+        )?;
 
         debug!(%hook, target = %info.env_path.display(), "Installing environment");
 
@@ -75,7 +77,12 @@ impl LanguageImpl for Python {
                 .env("VIRTUAL_ENV", &info.env_path)
                 .check(true)
                 .output()
-                .await?;
+                .await
+                .map_err(|err| {
+                    offline::or_missing(err, || {
+                        offline::Missing::dependencies(&hook, "Python package")
+                    })
+                })?;
         } else if !hook.additional_dependencies.is_empty() {
             uv.cmd("uv pip install", store)
                 .arg("pip")
@@ -84,7 +91,12 @@ impl LanguageImpl for Python {
                 .env("VIRTUAL_ENV", &info.env_path)
                 .check(true)
                 .output()
-                .await?;
+                .await
+                .map_err(|err| {
+                    offline::or_missing(err, || {
+                        offline::Missing::dependencies(&hook, "Python package")
+                    })
+                })?;
         } else {
             debug!("No dependencies to install");
         }
@@ -122,8 +134,10 @@ impl LanguageImpl for Python {
         })
     }
 
-    async fn check_health(&self) -> Result<()> {
-        todo!()
+    async fn check_health(&self, hook: &Hook, info: &InstallInfo) -> Result<()> {
+        // The venv interpreter links to the Python the venv was created from.
+        check_exists(&python_exec(&info.env_path))?;
+        check_entry(hook, &[&bin_dir(&info.env_path)])
     }
 
     async fn run(
@@ -138,20 +152,15 @@ impl LanguageImpl for Python {
 
         let run = async move |batch: Vec<String>| {
             // TODO: combine stdout and stderr
-            let mut output = Cmd::new(&entry[0], "python hook")
-                .args(&entry[1..])
+            let mut cmd = Cmd::new(&entry[0], "python hook");
+            cmd.args(&entry[1..])
                 .env("VIRTUAL_ENV", env_dir)
                 .env("PATH", &new_path)
                 .env_remove("PYTHONHOME")
                 .args(&hook.args)
-                .args(batch)
-                .check(false)
-                .output()
-                .await?;
+                .args(batch);
 
-            output.stdout.extend(output.stderr);
-            let code = output.status.code().unwrap_or(1);
-            anyhow::Ok((code, output.stdout))
+            run_hook_cmd(hook, &mut cmd).await
         };
 
         let results = run_by_batch(hook, filenames, run).await?;
@@ -192,6 +201,10 @@ impl Python {
             Err(e @ process::Error::Status { .. }) => {
                 // Check if we can retry with downloads
                 if Self::can_retry_with_downloads(&e) {
+                    if offline::is_enabled() {
+                        let python = python_request.map_or("any version", String::as_str);
+                        return Err(offline::Missing::new(format!("Python {python}")).into());
+                    }
                     debug!(
                         "Retrying venv creation with managed Python downloads: `{}`",
                         info.env_path.display()
@@ -226,7 +239,9 @@ impl Python {
             .arg("--python-preference")
             .arg("managed")
             .arg("--no-project")
-            .arg("--no-config");
+            .arg("--no-config")
+            // The venv is built in a staging directory and moved into place.
+            .arg("--relocatable");
 
         if set_install_dir {
             cmd.env(
@@ -236,6 +251,9 @@ impl Python {
         }
         if allow_downloads {
             cmd.arg("--allow-python-downloads");
+            if let Some(mirror) = Settings::get().mirrors.python() {
+                cmd.env(EnvVars::UV_PYTHON_INSTALL_MIRROR, mirror);
+            }
         } else {
             cmd.arg("--no-python-downloads");
         }
@@ -3,22 +3,62 @@ use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 use std::time::Duration;
 
-use anyhow::{Result, bail};
-use axoupdater::{AxoUpdater, ReleaseSource, ReleaseSourceType, UpdateRequest};
+use anyhow::{Context, Result, bail};
 use semver::Version;
 use std::process::Command;
+use target_lexicon::HOST;
 use tokio::task::JoinSet;
-use tracing::{debug, enabled, trace, warn};
+use tracing::{debug, trace, warn};
 
 use constants::env_vars::EnvVars;
 
+use crate::archive;
 use crate::fs::LockedFile;
+use crate::languages::{download_and_unpack, find_checksum};
+use crate::offline;
 use crate::process::Cmd;
+use crate::settings::Settings;
 use crate::store::{CacheBucket, Store};
 
 // The version range of `uv` we will install. Should update periodically.
 const CUR_UV_VERSION: &str = "0.8.6";
 const UV_VERSION_RANGE: &str = ">=0.7.0, <0.9.0";
+const GITHUB_RELEASES_URL: &str = "https://github.com/astral-sh/uv/releases/download";
+
+/// The targets uv publishes release archives for.
+const RELEASE_TARGETS: &[&str] = &[
+    "aarch64-apple-darwin",
+    "aarch64-pc-windows-msvc",
+    "aarch64-unknown-linux-gnu",
+    "aarch64-unknown-linux-musl",
+    "arm-unknown-linux-musleabihf",
+    "armv7-unknown-linux-gnueabihf",
+    "armv7-unknown-linux-musleabihf",
+    "i686-pc-windows-msvc",
+    "i686-unknown-linux-gnu",
+    "i686-unknown-linux-musl",
+    "powerpc64-unknown-linux-gnu",
+    "powerpc64le-unknown-linux-gnu",
+    "riscv64gc-unknown-linux-gnu",
+    "s390x-unknown-linux-gnu",
+    "x86_64-apple-darwin",
+    "x86_64-pc-windows-msvc",
+    "x86_64-unknown-linux-gnu",
+    "x86_64-unknown-linux-musl",
+];
+
+/// The name of the uv release archive for the given target, if uv publishes one.
+fn release_filename(target: &str) -> Option<String> {
+    if !RELEASE_TARGETS.contains(&target) {
+        return None;
+    }
+    let ext = if target.contains("windows") {
+        "zip"
+    } else {
+        "tar.gz"
+    };
+    Some(format!("uv-{target}.{ext}"))
+}
 
 fn get_uv_version(uv_path: &Path) -> Result<Version> {
     let output = Command::new(uv_path)
@@ -91,6 +131,8 @@ impl PyPiMirror {
 enum InstallSource {
     /// Download uv from GitHub releases.
     GitHub,
+    /// Download uv from a mirror of the GitHub releases.
+    Mirror(String),
     /// Download uv from `PyPi`.
     PyPi(PyPiMirror),
     /// Install uv by running `pip install uv`.
@@ -100,68 +142,63 @@ enum InstallSource {
 impl InstallSource {
     async fn install(&self, target: &Path) -> Result<()> {
         match self {
-            Self::GitHub => self.install_from_github(target).await,
+            Self::GitHub => self.install_from_github(target, GITHUB_RELEASES_URL).await,
+            Self::Mirror(url) => self.install_from_github(target, url).await,
             Self::PyPi(source) => self.install_from_pypi(target, source).await,
             Self::Pip => self.install_from_pip(target).await,
         }
     }
 
-    async fn install_from_github(&self, target: &Path) -> Result<()> {
-        let mut installer = AxoUpdater::new_for("uv");
-        installer
-            .configure_version_specifier(UpdateRequest::SpecificTag(CUR_UV_VERSION.to_string()));
-        installer.always_update(true);
-        installer.set_install_dir(&target.to_string_lossy());
-        installer.set_release_source(ReleaseSource {
-            release_type: ReleaseSourceType::GitHub,
-            owner: "astral-sh".to_string(),
-            name: "uv".to_string(),
-            app_name: "uv".to_string(),
-        });
-        if enabled!(tracing::Level::DEBUG) {
-            installer.enable_installer_output();
-            unsafe { env::set_var("INSTALLER_PRINT_VERBOSE", "1") };
-        } else {
-            installer.disable_installer_output();
-        }
-        // We don't want the installer to modify the PATH, and don't need the receipt.
-        unsafe { env::set_var("UV_UNMANAGED_INSTALL", "1") };
-
-        match installer.run().await {
-            Ok(Some(result)) => {
-                debug!(
-                    uv = %target.display(),
-                    version = result.new_version_tag,
-                    "Successfully installed uv"
-                );
-                Ok(())
-            }
-            Ok(None) => Ok(()),
-            Err(err) => {
-                warn!(?err, "Failed to install uv");
-                Err(err.into())
-            }
-        }
+    /// Download the uv release archive from GitHub or its mirror, verified against
+    /// its published sha256 checksum.
+    async fn install_from_github(&self, target: &Path, releases_url: &str) -> Result<()> {
+        let Some(filename) = release_filename(&HOST.to_string()) else {
+            bail!("uv does not publish release archives for `{HOST}`");
+        };
+        let url = format!("{releases_url}/{CUR_UV_VERSION}/{filename}");
+
+        let client = reqwest::Client::new();
+        let checksum_url = format!("{url}.sha256");
+        let checksums = client
+            .get(&checksum_url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("Failed to download checksum from {checksum_url}"))?
+            .text()
+            .await?;
+        let sha256 = find_checksum(&checksums, &filename)
+            .with_context(|| format!("No checksum found for `{filename}` in {checksum_url}"))?;
+
+        let temp_dir = download_and_unpack(&client, &url, &filename, sha256, target).await?;
+        move_executables(temp_dir.path(), target).await?;
+        debug!(
+            uv = %target.display(),
+            version = CUR_UV_VERSION,
+            "Successfully installed uv"
+        );
+
+        Ok(())
     }
 
-    async fn install_from_pypi(&self, target: &Path, _source: &PyPiMirror) -> Result<()> {
-        // TODO: Implement this, currently just fallback to pip install
-        // Determine the host system
-        // Get the html page
-        // Parse html, get the latest version url
-        // Download the tarball
-        // Extract the tarball
-        self.install_from_pip(target).await
+    async fn install_from_pypi(&self, target: &Path, source: &PyPiMirror) -> Result<()> {
+        // TODO: Download and extract the wheel directly, currently just `pip install` from the index.
+        self.pip_install(target, Some(source.url())).await
     }
 
     async fn install_from_pip(&self, target: &Path) -> Result<()> {
+        self.pip_install(target, None).await
+    }
+
+    async fn pip_install(&self, target: &Path, index_url: Option<&str>) -> Result<()> {
         // When running `pip install` in multiple threads, it can fail
         // without extracting files properly.
-        Cmd::new("python3", "pip install uv")
-            .arg("-m")
-            .arg("pip")
-            .arg("install")
-            .arg("--prefix")
+        let mut cmd = Cmd::new("python3", "pip install uv");
+        cmd.arg("-m").arg("pip").arg("install");
+        if let Some(index_url) = index_url {
+            cmd.arg("--index-url").arg(index_url);
+        }
+        cmd.arg("--prefix")
             .arg(target)
             .arg(format!("uv=={CUR_UV_VERSION}"))
             .check(true)
@@ -187,6 +224,28 @@ impl InstallSource {
     }
 }
 
+/// Move the executables of an unpacked uv release archive, `uv`, `uvx` and `uvw` on Windows,
+/// into the target directory.
+///
+/// The archive contains a `uv-<target>` directory on Unix, and the executables at the top
+/// level on Windows.
+async fn move_executables(unpacked: &Path, target: &Path) -> Result<()> {
+    let extracted = match archive::strip_component(unpacked) {
+        Ok(top_level) => top_level,
+        Err(archive::Error::NonSingularArchive(_)) => unpacked.to_path_buf(),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut entries = fs_err::tokio::read_dir(&extracted).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_file() {
+            fs_err::tokio::rename(entry.path(), target.join(entry.file_name())).await?;
+        }
+    }
+
+    Ok(())
+}
+
 pub(crate) struct Uv {
     path: PathBuf,
 }
@@ -199,14 +258,18 @@ impl Uv {
     pub(crate) fn cmd(&self, summary: &str, store: &Store) -> Cmd {
         let mut cmd = Cmd::new(&self.path, summary);
         cmd.env(EnvVars::UV_CACHE_DIR, store.cache_path(CacheBucket::Uv));
+        if offline::is_enabled() {
+            cmd.env(EnvVars::UV_OFFLINE, "1");
+        }
         cmd
     }
 
     async fn select_source() -> Result<InstallSource> {
         async fn check_github(client: &reqwest::Client) -> Result<bool> {
-            let url = format!(
-                "https://github.com/astral-sh/uv/releases/download/{CUR_UV_VERSION}/uv-x86_64-unknown-linux-gnu.tar.gz"
-            );
+            let Some(filename) = release_filename(&HOST.to_string()) else {
+                return Ok(false);
+            };
+            let url = format!("{GITHUB_RELEASES_URL}/{CUR_UV_VERSION}/{filename}");
             let response = client
                 .head(url)
                 .timeout(Duration::from_secs(3))
@@ -279,6 +342,10 @@ impl Uv {
             return Ok(Self::new(uv_path));
         }
 
+        if offline::is_enabled() {
+            return Err(offline::Missing::new(format!("uv {UV_VERSION_RANGE}")).into());
+        }
+
         // Install new managed uv with proper locking
         fs_err::tokio::create_dir_all(&uv_dir).await?;
         let _lock = LockedFile::acquire(uv_dir.join(".lock"), "uv").await?;
@@ -288,9 +355,48 @@ impl Uv {
             return Ok(Self::new(uv_path));
         }
 
-        let source = Self::select_source().await?;
+        let source = if let Some(url) = Settings::get().mirrors.uv() {
+            trace!(%url, "Using uv mirror");
+            InstallSource::Mirror(url)
+        } else {
+            Self::select_source().await?
+        };
         source.install(uv_dir).await?;
 
         Ok(Self::new(uv_path))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_filenames() {
+        assert_eq!(
+            release_filename("x86_64-unknown-linux-gnu").as_deref(),
+            Some("uv-x86_64-unknown-linux-gnu.tar.gz")
+        );
+        assert_eq!(
+            release_filename("aarch64-pc-windows-msvc").as_deref(),
+            Some("uv-aarch64-pc-windows-msvc.zip")
+        );
+        assert_eq!(release_filename("x86_64-unknown-freebsd"), None);
+    }
+
+    #[tokio::test]
+    async fn move_all_executables() -> Result<()> {
+        let unpacked = tempfile::tempdir()?;
+        let target = tempfile::tempdir()?;
+        let extracted = unpacked.path().join("uv-x86_64-unknown-linux-gnu");
+        fs_err::create_dir(&extracted)?;
+        fs_err::write(extracted.join("uv"), "")?;
+        fs_err::write(extracted.join("uvx"), "")?;
+
+        move_executables(unpacked.path(), target.path()).await?;
+        assert!(target.path().join("uv").is_file());
+        assert!(target.path().join("uvx").is_file());
+
+        Ok(())
+    }
+}
@@ -134,7 +134,6 @@ mod tests {
     use super::*;
     use crate::config::Language;
     use std::collections::HashSet;
-    use std::path::Path;
 
     #[test]
     fn test_parse_python_request() {
@@ -213,9 +212,10 @@ mod tests {
     }
 
     #[test]
-    fn test_satisfied_by() {
+    fn test_satisfied_by() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
         let mut install_info =
-            InstallInfo::new(Language::Python, HashSet::default(), Path::new("."));
+            InstallInfo::new(Language::Python, HashSet::default(), temp_dir.path())?;
         install_info
             .with_language_version(semver::Version::new(3, 12, 1))
             .with_toolchain(PathBuf::from("/usr/bin/python3.12"));
@@ -240,5 +240,7 @@ mod tests {
 
         let range_req = semver::VersionReq::parse(">=4.0").unwrap();
         assert!(!PythonRequest::Range(range_req, ">=4.0".to_string()).satisfied_by(&install_info));
+
+        Ok(())
     }
 }
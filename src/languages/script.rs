@@ -3,11 +3,10 @@ use std::sync::Arc;
 use anyhow::Result;
 
 use crate::fs::CWD;
-use crate::hook::Hook;
-use crate::hook::InstalledHook;
+use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{run_by_batch, run_hook_cmd};
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -18,7 +17,7 @@ impl LanguageImpl for Script {
         Ok(InstalledHook::NoNeedInstall(hook))
     }
 
-    async fn check_health(&self) -> Result<()> {
+    async fn check_health(&self, _hook: &Hook, _info: &InstallInfo) -> Result<()> {
         Ok(())
     }
 
@@ -29,20 +28,15 @@ impl LanguageImpl for Script {
         _store: &Store,
     ) -> Result<(i32, Vec<u8>)> {
         let entry = hook.entry.parsed()?;
-        let repo_path = hook.repo_path().unwrap_or_else(|| CWD.as_path());
+        let work_dir = CWD.join(hook.project_dir());
+        let repo_path = hook.repo_path().unwrap_or(work_dir.as_path());
         let cmd = repo_path.join(&entry[0]);
 
         let run = async move |batch: Vec<String>| {
-            let mut command = Cmd::new(&cmd, "run script command")
-                .args(&entry[1..])
-                .args(&hook.args)
-                .args(batch)
-                .output()
-                .await?;
-
-            command.stdout.extend(command.stderr);
-            let code = command.status.code().unwrap_or(1);
-            anyhow::Ok((code, command.stdout))
+            let mut command = Cmd::new(&cmd, "run script command");
+            command.args(&entry[1..]).args(&hook.args).args(batch);
+
+            run_hook_cmd(hook, &mut command).await
         };
 
         let results = run_by_batch(hook, filenames, run).await?;
@@ -2,10 +2,10 @@ use std::sync::Arc;
 
 use anyhow::Result;
 
-use crate::hook::{Hook, InstalledHook};
+use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{run_by_batch, run_hook_cmd};
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -16,7 +16,7 @@ impl LanguageImpl for System {
         Ok(InstalledHook::NoNeedInstall(hook))
     }
 
-    async fn check_health(&self) -> Result<()> {
+    async fn check_health(&self, _hook: &Hook, _info: &InstallInfo) -> Result<()> {
         Ok(())
     }
 
@@ -29,17 +29,10 @@ impl LanguageImpl for System {
         let entry = hook.entry.parsed()?;
 
         let run = async move |batch: Vec<String>| {
-            let mut output = Cmd::new(&entry[0], "run system command")
-                .args(&entry[1..])
-                .args(&hook.args)
-                .args(batch)
-                .check(false)
-                .output()
-                .await?;
-
-            output.stdout.extend(output.stderr);
-            let code = output.status.code().unwrap_or(1);
-            anyhow::Ok((code, output.stdout))
+            let mut cmd = Cmd::new(&entry[0], "run system command");
+            cmd.args(&entry[1..]).args(&hook.args).args(batch);
+
+            run_hook_cmd(hook, &mut cmd).await
         };
 
         let results = run_by_batch(hook, filenames, run).await?;
@@ -13,25 +13,35 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::filter::Directive;
 
 use crate::cleanup::cleanup;
-use crate::cli::{Cli, Command, ExitStatus, SelfCommand, SelfNamespace, SelfUpdateArgs};
+use crate::cli::{
+    BundleCommand, BundleExportArgs, BundleImportArgs, BundleNamespace, CacheCommand,
+    CacheImportPreCommitArgs, CacheNamespace, Cli, Command, ExitStatus, HookSelector, SelfCommand,
+    SelfNamespace, SelfUpdateArgs,
+};
 use crate::git::get_root;
 use crate::printer::Printer;
+use crate::settings::Settings;
 
 mod archive;
 mod builtin;
 mod cleanup;
 mod cli;
 mod config;
+mod diagnostic;
 mod fs;
 mod git;
 mod hook;
 mod identify;
 mod languages;
+mod offline;
 mod printer;
 mod process;
 #[cfg(all(unix, feature = "profiler"))]
 mod profiler;
+#[cfg(unix)]
+mod pty;
 mod run;
+mod settings;
 mod store;
 mod version;
 mod warnings;
@@ -92,6 +102,25 @@ fn adjust_relative_paths(cli: &mut Cli, new_cwd: &Path) -> Result<()> {
         }
     }
 
+    if let Some(Command::Bundle(BundleNamespace {
+        command:
+            BundleCommand::Export(BundleExportArgs { ref mut file })
+            | BundleCommand::Import(BundleImportArgs { ref mut file }),
+    })) = cli.command
+    {
+        *file = std::path::absolute(&*file)?;
+    }
+
+    if let Some(Command::Cache(CacheNamespace {
+        command:
+            CacheCommand::ImportPreCommit(CacheImportPreCommitArgs {
+                dir: Some(ref mut dir),
+            }),
+    })) = cli.command
+    {
+        *dir = std::path::absolute(&*dir)?;
+    }
+
     if let Some(Command::Run(ref mut args) | Command::TryRepo(ref mut args)) = cli.command {
         args.files = args
             .files
@@ -109,6 +138,11 @@ fn adjust_relative_paths(cli: &mut Cli, new_cwd: &Path) -> Result<()> {
                     .map(|p| p.to_string_lossy().to_string())
             })
             .collect::<Result<Vec<String>, std::io::Error>>()?;
+        args.cd = args
+            .cd
+            .as_ref()
+            .map(|path| fs::relative_to(std::path::absolute(path)?, new_cwd))
+            .transpose()?;
         args.extra.commit_msg_filename = args
             .extra
             .commit_msg_filename
@@ -124,7 +158,15 @@ fn adjust_relative_paths(cli: &mut Cli, new_cwd: &Path) -> Result<()> {
 }
 
 async fn run(mut cli: Cli) -> Result<ExitStatus> {
-    ColorChoice::write_global(cli.globals.color.into());
+    let settings = Settings::init()?;
+
+    ColorChoice::write_global(
+        cli.globals
+            .color
+            .or(settings.color)
+            .unwrap_or_default()
+            .into(),
+    );
 
     setup_logging(match cli.globals.verbose {
         0 => Level::Default,
@@ -138,12 +180,16 @@ async fn run(mut cli: Cli) -> Result<ExitStatus> {
         Printer::Quiet
     } else if cli.globals.verbose > 1 {
         Printer::Verbose
-    } else if cli.globals.no_progress {
+    } else if cli.globals.no_progress || settings.no_progress == Some(true) {
         Printer::NoProgress
     } else {
         Printer::Default
     };
 
+    if cli.globals.offline {
+        offline::enable();
+    }
+
     if cli.globals.quiet {
         warnings::disable();
     } else {
@@ -211,7 +257,7 @@ async fn run(mut cli: Cli) -> Result<ExitStatus> {
 
             cli::run(
                 cli.globals.config,
-                args.hook_id,
+                HookSelector::from_args(&args),
                 args.hook_stage,
                 args.from_ref,
                 args.to_ref,
@@ -220,6 +266,8 @@ async fn run(mut cli: Cli) -> Result<ExitStatus> {
                 args.directory,
                 args.last_commit,
                 args.show_diff_on_failure,
+                args.dry_run,
+                args.cd,
                 args.extra,
                 cli.globals.verbose > 0,
                 printer,
@@ -239,18 +287,51 @@ async fn run(mut cli: Cli) -> Result<ExitStatus> {
             )
             .await
         }
+        Command::Bundle(BundleNamespace { command }) => match command {
+            BundleCommand::Export(args) => {
+                show_settings!(args);
+
+                cli::bundle_export(cli.globals.config, &args.file, printer).await
+            }
+            BundleCommand::Import(args) => {
+                show_settings!(args);
+
+                cli::bundle_import(&args.file, printer).await
+            }
+        },
+        Command::Cache(CacheNamespace { command }) => match command {
+            CacheCommand::Dir => cli::cache_dir(printer),
+            CacheCommand::Size => cli::cache_size(printer),
+            CacheCommand::List => cli::cache_list(printer),
+            CacheCommand::Remove(args) => {
+                show_settings!(args);
+
+                cli::cache_remove(&args.entries, printer).await
+            }
+            CacheCommand::ImportPreCommit(args) => {
+                show_settings!(args);
+
+                cli::cache_import_pre_commit(args.dir, printer).await
+            }
+        },
+        Command::Doctor => cli::doctor(cli.globals.config, printer).await,
         Command::Clean => cli::clean(printer),
         Command::ValidateConfig(args) => {
             show_settings!(args);
 
-            Ok(cli::validate_configs(args.configs))
+            cli::validate_configs(args.configs, args.strict, args.check_remote, printer).await
         }
         Command::ValidateManifest(args) => {
             show_settings!(args);
 
             Ok(cli::validate_manifest(args.manifests))
         }
-        Command::SampleConfig(args) => cli::sample_config(args.file, printer),
+        Command::SampleConfig(args) => cli::sample_config(args.file, args.format, printer),
+        Command::ConvertConfig(args) => {
+            show_settings!(args);
+
+            cli::convert_config(&args.input, args.output, printer)
+        }
         Command::Self_(SelfNamespace {
             command:
                 SelfCommand::Update(SelfUpdateArgs {
@@ -269,6 +350,11 @@ async fn run(mut cli: Cli) -> Result<ExitStatus> {
             clap_complete::generate(args.shell, &mut command, bin_name, &mut std::io::stdout());
             Ok(ExitStatus::Success)
         }
+        Command::GenerateJsonSchema(args) => {
+            show_settings!(args);
+
+            cli::generate_json_schema(args.kind)
+        }
         Command::InitTemplateDir(args) => {
             show_settings!(args);
 
@@ -340,6 +426,9 @@ fn main() -> ExitCode {
             for err in causes {
                 eprintln!("  {}: {}", "caused by".red().bold(), err);
             }
+            if let Some(snippet) = diagnostic::render(err.as_ref()) {
+                eprintln!("\n{snippet}");
+            }
             ExitStatus::Error.into()
         }
     }
@@ -0,0 +1,94 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tracing::debug;
+
+use crate::hook::Hook;
+
+/// Whether offline mode is enabled, set by `--offline` or `PREK_OFFLINE`.
+///
+/// In offline mode, prek never accesses the network: repos, toolchains and dependencies
+/// are only resolved from the store or the system.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enable offline mode.
+pub(crate) fn enable() {
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Whether offline mode is enabled.
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Things that are needed but not available locally, in offline mode.
+#[derive(Debug, Default, thiserror::Error)]
+pub(crate) struct Missing(BTreeSet<String>);
+
+impl Missing {
+    pub(crate) fn new(item: impl Into<String>) -> Self {
+        Self(BTreeSet::from([item.into()]))
+    }
+
+    /// The dependencies of a hook, `kind` describes a dependency like `npm package`.
+    ///
+    /// The repo of the hook is included if it's installed as a package too.
+    pub(crate) fn dependencies(hook: &Hook, kind: &str) -> Self {
+        let mut missing: Self = hook
+            .additional_dependencies
+            .iter()
+            .map(|dep| format!("{kind} `{dep}`"))
+            .collect();
+        if hook.repo_path().is_some() {
+            missing
+                .0
+                .insert(format!("dependencies of repo `{}`", hook.repo()));
+        }
+        missing
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Merge the missing items of another error.
+    pub(crate) fn extend(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+}
+
+/// Replace an error installing dependencies with the dependencies that are missing.
+///
+/// In offline mode, installers only read from their local caches, so a failure almost
+/// always means the dependencies are not cached.
+pub(crate) fn or_missing(
+    err: impl Into<anyhow::Error>,
+    missing: impl FnOnce() -> Missing,
+) -> anyhow::Error {
+    let err = err.into();
+    if !is_enabled() {
+        return err;
+    }
+    debug!("Failed to install dependencies in offline mode: {err:#}");
+    missing().into()
+}
+
+impl FromIterator<String> for Missing {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Display for Missing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Offline mode is enabled, but the following are not available locally:"
+        )?;
+        for item in &self.0 {
+            write!(f, "\n  - {item}")?;
+        }
+        Ok(())
+    }
+}
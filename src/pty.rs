@@ -0,0 +1,152 @@
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::pin::Pin;
+use std::process::Stdio;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, ReadBuf};
+use tracing::trace;
+
+use crate::process::Cmd;
+
+/// A pseudo-terminal pair, used to run a command as if it was attached to a terminal.
+///
+/// The child writes both stdout and stderr to the slave side, so the output is merged in the
+/// order it's produced, while tools that check `isatty` keep their colored output.
+pub(crate) struct Pty {
+    master: OwnedFd,
+    slave: OwnedFd,
+}
+
+impl Pty {
+    /// Open a new pseudo-terminal with the same window size as our stdout.
+    pub(crate) fn open() -> io::Result<Self> {
+        let mut master: RawFd = -1;
+        let mut slave: RawFd = -1;
+
+        let mut size = libc::winsize {
+            ws_row: 24,
+            ws_col: 80,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        unsafe {
+            libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &raw mut size);
+        }
+
+        let ret = unsafe {
+            libc::openpty(
+                &raw mut master,
+                &raw mut slave,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                &raw mut size,
+            )
+        };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let (master, slave) =
+            unsafe { (OwnedFd::from_raw_fd(master), OwnedFd::from_raw_fd(slave)) };
+        set_cloexec(&master)?;
+        set_cloexec(&slave)?;
+        disable_onlcr(&slave)?;
+
+        Ok(Self { master, slave })
+    }
+
+    /// Spawn the command with its stdout and stderr attached to the pseudo-terminal.
+    pub(crate) fn spawn(&self, cmd: &mut Cmd) -> anyhow::Result<tokio::process::Child> {
+        let stdout = self.slave.try_clone()?;
+        let stderr = self.slave.try_clone()?;
+
+        let child = cmd
+            .stdin(Stdio::null())
+            .stdout(stdout)
+            .stderr(stderr)
+            .spawn();
+
+        // The command holds the slave descriptors until it's dropped, release them so that
+        // reading from the master side ends once the child exits.
+        cmd.stdout(Stdio::null()).stderr(Stdio::null());
+
+        Ok(child?)
+    }
+
+    /// Return a reader for the output written to the pseudo-terminal.
+    ///
+    /// The reader reaches EOF once all processes holding the slave side have exited.
+    pub(crate) fn into_reader(self) -> impl AsyncRead + Unpin {
+        drop(self.slave);
+        let master = tokio::fs::File::from_std(std::fs::File::from(self.master));
+        PtyReader(master)
+    }
+}
+
+fn set_cloexec(fd: &OwnedFd) -> io::Result<()> {
+    let ret = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) };
+    if ret == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Don't translate `\n` into `\r\n`, keep the output as if it's written to a pipe.
+fn disable_onlcr(fd: &OwnedFd) -> io::Result<()> {
+    let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+    if unsafe { libc::tcgetattr(fd.as_raw_fd(), termios.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let mut termios = unsafe { termios.assume_init() };
+    termios.c_oflag &= !libc::ONLCR;
+    if unsafe { libc::tcsetattr(fd.as_raw_fd(), libc::TCSANOW, &raw const termios) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Reading from the master side fails with `EIO` once the slave side is closed,
+/// treat it as EOF.
+struct PtyReader(tokio::fs::File);
+
+impl AsyncRead for PtyReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match Pin::new(&mut self.0).poll_read(cx, buf) {
+            Poll::Ready(Err(err)) if err.raw_os_error() == Some(libc::EIO) => {
+                trace!("Pseudo-terminal closed");
+                Poll::Ready(Ok(()))
+            }
+            poll => poll,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn run_in_pty() -> anyhow::Result<()> {
+        let pty = Pty::open()?;
+        let mut cmd = Cmd::new("sh", "check tty");
+        cmd.arg("-c")
+            .arg("test -t 1 && echo stdout is a tty; test -t 2 && echo stderr is a tty >&2");
+
+        let mut child = pty.spawn(&mut cmd)?;
+        let mut output = String::new();
+        pty.into_reader().read_to_string(&mut output).await?;
+        let status = child.wait().await?;
+
+        assert!(status.success());
+        assert_eq!(output, "stdout is a tty\nstderr is a tty\n");
+
+        Ok(())
+    }
+}
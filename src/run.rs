@@ -1,18 +1,26 @@
 use std::cmp::max;
 use std::ffi::OsString;
+use std::io::Write;
 use std::path::Path;
-use std::sync::LazyLock;
+use std::process::Stdio;
+use std::sync::{LazyLock, Mutex};
 
 use futures::StreamExt;
+use owo_colors::OwoColorize;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tracing::trace;
 
 use constants::env_vars::EnvVars;
 
 use crate::hook::Hook;
+use crate::process::Cmd;
+use crate::settings::Settings;
 
 pub(crate) static CONCURRENCY: LazyLock<usize> = LazyLock::new(|| {
     if EnvVars::is_set(EnvVars::PREK_NO_CONCURRENCY) {
         1
+    } else if let Some(concurrency) = Settings::get().concurrency {
+        max(concurrency, 1)
     } else {
         std::thread::available_parallelism()
             .map(std::num::NonZero::get)
@@ -101,6 +109,18 @@ impl<'a> Iterator for Partitions<'a> {
     }
 }
 
+/// Split the filenames into batches the same way as `run_by_batch`, without running anything.
+///
+/// Returns the number of batches that may run concurrently and the batches.
+pub(crate) fn batches<'a>(
+    hook: &'a Hook,
+    filenames: &'a [&'a String],
+) -> (usize, Vec<&'a [&'a String]>) {
+    let concurrency = target_concurrency(hook.require_serial);
+    let partitions = Partitions::new(hook, filenames, concurrency);
+    (concurrency, partitions.collect())
+}
+
 pub(crate) async fn run_by_batch<T, F>(
     hook: &Hook,
     filenames: &[&String],
@@ -137,6 +157,115 @@ where
     Ok(results)
 }
 
+/// Whether to run hooks in a pseudo-terminal, so that they keep their colored output.
+///
+/// This is opt-in with `PREK_PTY`, and only used when our own stdout supports colors.
+#[cfg(unix)]
+static USE_PTY: LazyLock<bool> = LazyLock::new(|| {
+    use anstream::{AutoStream, ColorChoice};
+
+    EnvVars::is_set(EnvVars::PREK_PTY)
+        && matches!(
+            AutoStream::choice(&std::io::stdout()),
+            ColorChoice::Always | ColorChoice::AlwaysAnsi
+        )
+});
+
+/// Run a hook command, returning its exit code and the combined stdout and stderr.
+///
+/// If the hook output is streamed, each line is also written to stdout as soon as it is
+/// produced, prefixed with the hook id.
+pub(crate) async fn run_hook_cmd(hook: &Hook, cmd: &mut Cmd) -> anyhow::Result<(i32, Vec<u8>)> {
+    cmd.current_dir(hook.work_dir()).check(false);
+
+    #[cfg(unix)]
+    if *USE_PTY {
+        return run_hook_cmd_in_pty(hook, cmd).await;
+    }
+
+    if !hook.stream {
+        let mut output = cmd.output().await?;
+        // To be consistent with pre-commit, merge stderr into stdout.
+        output.stdout.extend(output.stderr);
+        let code = output.status.code().unwrap_or(1);
+        return Ok((code, output.stdout));
+    }
+
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+
+    let output = Mutex::new(Vec::new());
+    let (status, stdout, stderr) = tokio::join!(
+        child.wait(),
+        read_output(hook, stdout, &output),
+        read_output(hook, stderr, &output),
+    );
+    stdout?;
+    stderr?;
+
+    let code = status?.code().unwrap_or(1);
+    Ok((code, output.into_inner().unwrap()))
+}
+
+/// Run a hook command with its stdout and stderr attached to a pseudo-terminal.
+#[cfg(unix)]
+async fn run_hook_cmd_in_pty(hook: &Hook, cmd: &mut Cmd) -> anyhow::Result<(i32, Vec<u8>)> {
+    let pty = crate::pty::Pty::open()?;
+    let mut child = pty.spawn(cmd)?;
+
+    let output = Mutex::new(Vec::new());
+    let (status, read) = tokio::join!(child.wait(), read_output(hook, pty.into_reader(), &output));
+    read?;
+
+    let code = status?.code().unwrap_or(1);
+    Ok((code, output.into_inner().unwrap()))
+}
+
+/// Read the output of a hook line by line into `output`, forwarding it to stdout if the
+/// hook output is streamed.
+async fn read_output(
+    hook: &Hook,
+    reader: impl AsyncRead + Unpin,
+    output: &Mutex<Vec<u8>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line).await? == 0 {
+            return Ok(());
+        }
+        if hook.stream {
+            write_streamed_line(hook, &line)?;
+        }
+        output.lock().unwrap().extend_from_slice(&line);
+    }
+}
+
+/// Write the output of a hook to stdout line by line, as if it was streamed.
+pub(crate) fn write_streamed(hook: &Hook, output: &[u8]) -> std::io::Result<()> {
+    for line in output.split_inclusive(|&b| b == b'\n') {
+        write_streamed_line(hook, line)?;
+    }
+    Ok(())
+}
+
+fn write_streamed_line(hook: &Hook, line: &[u8]) -> std::io::Result<()> {
+    // Hold the lock for the whole line, so that lines from concurrent batches don't interleave.
+    let mut stdout = anstream::stdout().lock();
+    write!(stdout, "{} ", format!("[{}]", hook.id).dimmed())?;
+    stdout.write_all(line)?;
+    if !line.ends_with(b"\n") {
+        stdout.write_all(b"\n")?;
+    }
+    stdout.flush()
+}
+
 pub(crate) fn prepend_paths(paths: &[&Path]) -> Result<OsString, std::env::JoinPathsError> {
     std::env::join_paths(
         paths.iter().map(|p| p.to_path_buf()).chain(
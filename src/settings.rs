@@ -0,0 +1,141 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use etcetera::BaseStrategy;
+use serde::Deserialize;
+use tracing::debug;
+
+use constants::env_vars::EnvVars;
+
+use crate::cli::ColorChoice;
+use crate::config::{self, Config};
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// User-level settings, read from `config.toml` in the prek config directory.
+///
+/// Every setting is a default: command line arguments and environment variables take
+/// precedence, and so do the project configs.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Settings {
+    /// Whether to use color in output.
+    pub(crate) color: Option<ColorChoice>,
+    /// Hide all progress outputs.
+    pub(crate) no_progress: Option<bool>,
+    /// The maximum number of hook processes to run in parallel.
+    pub(crate) concurrency: Option<usize>,
+    /// The directory to store repos, environments and toolchains, same as `PREK_HOME`.
+    pub(crate) home: Option<PathBuf>,
+    /// Whether to use the builtin Rust implementation of the `pre-commit-hooks` hooks.
+    pub(crate) fast_path: Option<bool>,
+    /// Mirrors to download toolchains from.
+    #[serde(default)]
+    pub(crate) mirrors: Mirrors,
+    /// Repos with hooks to run in every project.
+    #[serde(default)]
+    pub(crate) repos: Vec<config::Repo>,
+}
+
+/// Mirrors to download toolchains from, each can be overridden by an environment variable.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Mirrors {
+    /// The Node.js distribution mirror, defaults to `https://nodejs.org/dist`.
+    node: Option<String>,
+    /// The Go download mirror, defaults to `https://go.dev/dl`.
+    ///
+    /// Versions and checksums are read from its release feed, `?mode=json&include=all`.
+    go: Option<String>,
+    /// The uv release mirror, laid out like `https://github.com/astral-sh/uv/releases/download`.
+    uv: Option<String>,
+    /// The python-build-standalone mirror used by uv to download Python, laid out like
+    /// `https://github.com/astral-sh/python-build-standalone/releases/download`.
+    python: Option<String>,
+}
+
+impl Mirrors {
+    /// The Node.js distribution mirror, from `PREK_NODE_MIRROR` or the settings.
+    pub(crate) fn node(&self) -> Option<String> {
+        Self::resolve(EnvVars::PREK_NODE_MIRROR, self.node.as_deref())
+    }
+
+    /// The Go download mirror, from `PREK_GO_MIRROR` or the settings.
+    pub(crate) fn go(&self) -> Option<String> {
+        Self::resolve(EnvVars::PREK_GO_MIRROR, self.go.as_deref())
+    }
+
+    /// The uv release mirror, from `PREK_UV_MIRROR` or the settings.
+    pub(crate) fn uv(&self) -> Option<String> {
+        Self::resolve(EnvVars::PREK_UV_MIRROR, self.uv.as_deref())
+    }
+
+    /// The python-build-standalone mirror, from `PREK_PYTHON_MIRROR` or the settings.
+    pub(crate) fn python(&self) -> Option<String> {
+        Self::resolve(EnvVars::PREK_PYTHON_MIRROR, self.python.as_deref())
+    }
+
+    /// The environment variable takes precedence over the setting, the trailing slash is removed.
+    fn resolve(var: &str, setting: Option<&str>) -> Option<String> {
+        let url = EnvVars::var(var)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .or_else(|| setting.map(ToString::to_string))?;
+        Some(url.trim_end_matches('/').to_string())
+    }
+}
+
+impl Settings {
+    /// The path of the settings file: `$PREK_CONFIG_HOME/config.toml`,
+    /// or `~/.config/prek/config.toml` by default.
+    pub(crate) fn path() -> Option<PathBuf> {
+        let dir = if let Some(dir) = EnvVars::var_os(EnvVars::PREK_CONFIG_HOME) {
+            PathBuf::from(dir)
+        } else {
+            etcetera::choose_base_strategy()
+                .ok()?
+                .config_dir()
+                .join("prek")
+        };
+        Some(dir.join("config.toml"))
+    }
+
+    /// Read the settings file, returns the default settings if it does not exist.
+    fn read(path: &Path) -> Result<Self> {
+        let content = match fs_err::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+        debug!(path = %path.display(), "Loading global settings");
+
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse global config `{}`", path.display()))
+    }
+
+    /// Load the settings file, must be called before any settings are read.
+    pub(crate) fn init() -> Result<&'static Self> {
+        let settings = match Self::path() {
+            Some(path) => Self::read(&path)?,
+            None => Self::default(),
+        };
+        Ok(SETTINGS.get_or_init(|| settings))
+    }
+
+    /// Get the loaded settings.
+    pub(crate) fn get() -> &'static Self {
+        SETTINGS.get_or_init(Self::default)
+    }
+
+    /// A config with the global repos, to be inherited by every project config.
+    pub(crate) fn base_config(&self) -> Option<Config> {
+        if self.repos.is_empty() {
+            return None;
+        }
+        Some(Config {
+            repos: self.repos.clone(),
+            ..Config::default()
+        })
+    }
+}
@@ -1,19 +1,26 @@
-use std::hash::{DefaultHasher, Hash, Hasher};
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock, Mutex};
 
 use anyhow::Result;
+use bstr::ByteSlice;
 use etcetera::BaseStrategy;
+use itertools::Itertools;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
 use thiserror::Error;
-use tracing::debug;
+use tracing::{debug, trace, warn};
 
 use constants::env_vars::EnvVars;
 
 use crate::config::RemoteRepo;
 use crate::fs::LockedFile;
-use crate::git::clone_repo;
-use crate::hook::InstallInfo;
+use crate::git::{clone_repo, get_head_commit, git_cmd};
+use crate::hook::{BROKEN_MARKER, Hook, InstallInfo, STAGING_DIR};
+use crate::offline;
+use crate::settings::Settings;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -29,8 +36,19 @@ pub enum Error {
     Git(#[from] crate::git::Error),
     #[error(transparent)]
     Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Offline(#[from] offline::Missing),
+    #[error(
+        "The store was created by a newer version of prek (store version {0}), upgrade prek or run `prek clean`"
+    )]
+    UnsupportedVersion(u32),
 }
 
+/// The version of the store layout, bumped when the layout changes.
+///
+/// Stores of older versions are migrated when the store is initialized.
+const STORE_VERSION: u32 = 1;
+
 static STORE_HOME: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
     if let Some(path) = EnvVars::var_os(EnvVars::PREK_HOME) {
         debug!(
@@ -38,6 +56,9 @@ static STORE_HOME: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
             "Loading store from PREK_HOME env var",
         );
         Some(path.into())
+    } else if let Some(path) = &Settings::get().home {
+        debug!(path = %path.display(), "Loading store from global settings");
+        Some(path.clone())
     } else {
         etcetera::choose_base_strategy()
             .map(|path| path.cache_dir().join("prek"))
@@ -45,6 +66,16 @@ static STORE_HOME: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
     }
 });
 
+/// The locks of the resources in the store, held by the tasks of this process.
+static TASK_LOCKS: LazyLock<Mutex<FxHashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(Mutex::default);
+
+/// A lock of a resource in the store, held by one task of one process at a time.
+pub(crate) struct ResourceLock {
+    _file: LockedFile,
+    _task: tokio::sync::OwnedMutexGuard<()>,
+}
+
 /// A store for managing repos.
 #[derive(Debug)]
 pub struct Store {
@@ -66,8 +97,8 @@ impl Store {
         self.path.as_ref()
     }
 
-    /// Initialize the store.
-    pub(crate) fn init(self) -> Result<Self, Error> {
+    /// Initialize the store, migrating it to the current layout if needed.
+    pub(crate) async fn init(self) -> Result<Self, Error> {
         fs_err::create_dir_all(&self.path)?;
 
         match fs_err::OpenOptions::new()
@@ -78,14 +109,169 @@ impl Store {
             Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => (),
             Err(err) => return Err(err.into()),
         }
+
+        self.migrate().await?;
+        self.remove_stale_envs().await;
         Ok(self)
     }
 
+    /// Remove hook environments left in the staging directory by interrupted installs, and
+    /// environments marked as broken.
+    ///
+    /// Environments are only built and used while the store is locked, so they can be
+    /// removed if no other process is using the store. This is best effort, failures are
+    /// only logged.
+    async fn remove_stale_envs(&self) {
+        let _lock = match LockedFile::try_acquire(self.path.join(".lock"), "store") {
+            Ok(Some(lock)) => lock,
+            Ok(None) => {
+                debug!("Store is in use, skipping cleanup of stale hook environments");
+                return;
+            }
+            Err(err) => {
+                debug!(%err, "Failed to lock the store, skipping cleanup of stale hook environments");
+                return;
+            }
+        };
+
+        let staging = self.hooks_dir().join(STAGING_DIR);
+        let mut stale = fs_err::read_dir(&staging)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        stale.extend(
+            fs_err::read_dir(self.hooks_dir())
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.join(BROKEN_MARKER).exists()),
+        );
+
+        for path in stale {
+            debug!(path = %path.display(), "Removing stale hook environment");
+            match fs_err::tokio::remove_dir_all(&path).await {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    warn!(path = %path.display(), %err, "Failed to remove stale hook environment");
+                }
+            }
+        }
+    }
+
+    /// Read the version of the store layout, `None` if the store has no version file.
+    fn version(&self) -> Result<Option<u32>, Error> {
+        match fs_err::read_to_string(self.path.join(".version")) {
+            Ok(content) => Ok(content.trim().parse().ok()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Migrate the store to the current layout.
+    async fn migrate(&self) -> Result<(), Error> {
+        match self.version()? {
+            Some(STORE_VERSION) => return Ok(()),
+            Some(version) if version > STORE_VERSION => {
+                return Err(Error::UnsupportedVersion(version));
+            }
+            _ => {}
+        }
+
+        let _lock = self.lock_async().await?;
+        // Check again, another process may have migrated the store while we waited for the lock.
+        let version = match self.version()? {
+            Some(version) => version,
+            // Stores created before the layout was versioned have no version file.
+            None if self.repos_dir().try_exists()? => 0,
+            None => STORE_VERSION,
+        };
+        if version > STORE_VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        if version < 1 {
+            debug!("Migrating store to version 1");
+            self.migrate_v1().await?;
+        }
+
+        fs_err::write(self.path.join(".version"), STORE_VERSION.to_string())?;
+        Ok(())
+    }
+
+    /// Version 0 keyed cloned repos by a hash of the repo URL and rev that wasn't stable across
+    /// Rust versions. Re-key them by the repo URL and the commit they have checked out.
+    async fn migrate_v1(&self) -> Result<(), Error> {
+        #[derive(Deserialize)]
+        struct LegacyRepo {
+            repo: String,
+            rev: String,
+        }
+
+        let entries = fs_err::read_dir(self.repos_dir())?.collect::<Result<Vec<_>, _>>()?;
+        for entry in entries {
+            let path = entry.path();
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+
+            let legacy = fs_err::read(path.join(".prek-repo.json"))
+                .ok()
+                .and_then(|content| serde_json::from_slice::<LegacyRepo>(&content).ok());
+            let Some(legacy) = legacy else {
+                debug!(path = %path.display(), "Removing incomplete clone");
+                fs_err::tokio::remove_dir_all(&path).await?;
+                continue;
+            };
+            let commit = match get_head_commit(&path).await {
+                Ok(commit) => commit,
+                Err(err) => {
+                    warn!(path = %path.display(), %err, "Failed to resolve commit of cloned repo, removing it");
+                    fs_err::tokio::remove_dir_all(&path).await?;
+                    continue;
+                }
+            };
+
+            let target = self.repo_path(&legacy.repo, &commit);
+            if target.try_exists()? {
+                fs_err::tokio::remove_dir_all(&path).await?;
+            } else {
+                fs_err::tokio::rename(&path, &target).await?;
+            }
+            self.write_repo_info(&target, &legacy.repo, &commit).await?;
+            if legacy.rev != commit {
+                self.write_rev(&legacy.repo, &legacy.rev, &commit).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Clone a remote repo into the store.
-    pub(crate) async fn clone_repo(&self, repo: &RemoteRepo) -> Result<PathBuf, Error> {
+    ///
+    /// Clones are keyed by the repo URL and the commit the rev resolves to, so revs resolving
+    /// to the same commit share a clone.
+    ///
+    /// `on_wait` is called if another process is cloning the same repo.
+    pub(crate) async fn clone_repo(
+        &self,
+        repo: &RemoteRepo,
+        on_wait: impl FnOnce(),
+    ) -> Result<PathBuf, Error> {
         // Check if the repo is already cloned.
-        let target = self.repo_path(repo);
-        if target.join(".prek-repo.json").try_exists()? {
+        if let Some(target) = self.find_repo(repo)? {
+            return Ok(target);
+        }
+        if offline::is_enabled() {
+            return Err(offline::Missing::new(format!("repo `{repo}`")).into());
+        }
+
+        let _lock = self.lock_repo(repo, on_wait).await?;
+        // Another process may have cloned the repo while we waited for the lock.
+        if let Some(target) = self.find_repo(repo)? {
             return Ok(target);
         }
 
@@ -98,19 +284,132 @@ impl Store {
             %repo,
             "Cloning repo",
         );
-        clone_repo(repo.repo.as_str(), &repo.rev, temp.path()).await?;
+        let url = repo.repo.as_str();
+        clone_repo(url, &repo.rev, temp.path()).await?;
+        let commit = get_head_commit(temp.path()).await?;
 
-        // TODO: add windows retry
-        fs_err::tokio::remove_dir_all(&target).await.ok();
-        fs_err::tokio::rename(temp, &target).await?;
+        self.add_clone(temp, url, &repo.rev, &commit).await
+    }
 
-        let content = serde_json::to_string_pretty(&repo)?;
-        fs_err::tokio::write(target.join(".prek-repo.json"), content).await?;
+    /// Clone a remote repo into the store from an existing clone of it checked out at the rev,
+    /// like the ones in the pre-commit cache.
+    ///
+    /// Returns `None` if the repo is already in the store.
+    pub(crate) async fn import_repo(
+        &self,
+        repo: &RemoteRepo,
+        source: &Path,
+    ) -> Result<Option<PathBuf>, Error> {
+        if self.find_repo(repo)?.is_some() {
+            return Ok(None);
+        }
+
+        let _lock = self.lock_repo(repo, || {}).await?;
+        if self.find_repo(repo)?.is_some() {
+            return Ok(None);
+        }
+
+        fs_err::tokio::create_dir_all(self.repos_dir()).await?;
+
+        // Clone from the existing clone instead of copying it, to leave out anything
+        // else in its working tree, like the hook environments pre-commit installs there.
+        let commit = get_head_commit(source).await?;
+        let temp = tempfile::tempdir_in(self.repos_dir())?;
+        debug!(
+            target = %temp.path().display(),
+            source = %source.display(),
+            %repo,
+            "Importing repo",
+        );
+        let url = repo.repo.as_str();
+        clone_repo(&source.to_string_lossy(), &commit, temp.path()).await?;
+        git_cmd("set git remote")?
+            .current_dir(temp.path())
+            .arg("remote")
+            .arg("set-url")
+            .arg("origin")
+            .arg(url)
+            .check(true)
+            .output()
+            .await
+            .map_err(crate::git::Error::from)?;
+
+        self.add_clone(temp, url, &repo.rev, &commit)
+            .await
+            .map(Some)
+    }
+
+    /// Move a fresh clone of a repo at `commit` into the store, and record the commit `rev`
+    /// resolved to.
+    async fn add_clone(
+        &self,
+        clone: TempDir,
+        url: &str,
+        rev: &str,
+        commit: &str,
+    ) -> Result<PathBuf, Error> {
+        let target = self.repo_path(url, commit);
+        if target.join(".prek-repo.json").try_exists()? {
+            debug!(target = %target.display(), %commit, "Commit already cloned");
+        } else {
+            // TODO: add windows retry
+            fs_err::tokio::remove_dir_all(&target).await.ok();
+            fs_err::tokio::rename(clone, &target).await?;
+            self.write_repo_info(&target, url, commit).await?;
+        }
+        if rev != commit {
+            self.write_rev(url, rev, commit).await?;
+        }
 
         Ok(target)
     }
 
-    /// Returns installed hooks in the store.
+    /// Whether the remote repo is already cloned into the store.
+    pub(crate) fn is_cloned(&self, repo: &RemoteRepo) -> bool {
+        matches!(self.find_repo(repo), Ok(Some(_)))
+    }
+
+    /// Returns the path to the cloned repo, if it is in the store.
+    fn find_repo(&self, repo: &RemoteRepo) -> Result<Option<PathBuf>, Error> {
+        let url = repo.repo.as_str();
+        let commit = if is_commit(&repo.rev) {
+            repo.rev.clone()
+        } else {
+            match fs_err::read_to_string(self.rev_path(url, &repo.rev)) {
+                Ok(commit) => commit.trim().to_string(),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(err.into()),
+            }
+        };
+
+        let target = self.repo_path(url, &commit);
+        Ok(target
+            .join(".prek-repo.json")
+            .try_exists()?
+            .then_some(target))
+    }
+
+    async fn write_repo_info(&self, target: &Path, url: &str, commit: &str) -> Result<(), Error> {
+        let info = RepoInfo {
+            repo: url.to_string(),
+            commit: commit.to_string(),
+        };
+        let content = serde_json::to_string_pretty(&info)?;
+        fs_err::tokio::write(target.join(".prek-repo.json"), content).await?;
+        Ok(())
+    }
+
+    /// Record the commit a rev of a repo resolved to.
+    async fn write_rev(&self, url: &str, rev: &str, commit: &str) -> Result<(), Error> {
+        let path = self.rev_path(url, rev);
+        if let Some(parent) = path.parent() {
+            fs_err::tokio::create_dir_all(parent).await?;
+        }
+        fs_err::tokio::write(path, commit).await?;
+        Ok(())
+    }
+
+    /// Returns installed hooks in the store, leaving out the ones marked as broken.
     pub(crate) fn installed_hooks(&self) -> impl Iterator<Item = InstallInfo> {
         fs_err::read_dir(self.hooks_dir())
             .ok()
@@ -122,23 +421,112 @@ impl Store {
                 let mut file = fs_err::File::open(path.join(".prek-hook.json")).ok()?;
                 serde_json::from_reader(&mut file).ok()
             })
+            .filter(|info: &InstallInfo| !info.is_broken())
     }
 
-    /// Lock the store.
+    /// Returns the cloned repos in the store, with their paths.
+    pub(crate) fn cloned_repos(&self) -> impl Iterator<Item = (PathBuf, RepoInfo)> {
+        fs_err::read_dir(self.repos_dir())
+            .ok()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let mut file = fs_err::File::open(path.join(".prek-repo.json")).ok()?;
+                let info = serde_json::from_reader(&mut file).ok()?;
+                Some((path, info))
+            })
+    }
+
+    /// Lock the store exclusively, for changes to its layout.
     pub(crate) fn lock(&self) -> Result<LockedFile, std::io::Error> {
         LockedFile::acquire_blocking(self.path.join(".lock"), "store")
     }
 
+    /// Lock the store exclusively, for changes to its layout.
     pub(crate) async fn lock_async(&self) -> Result<LockedFile, std::io::Error> {
         LockedFile::acquire(self.path.join(".lock"), "store").await
     }
 
-    /// Returns the path to the cloned repo.
-    fn repo_path(&self, repo: &RemoteRepo) -> PathBuf {
-        let mut hasher = DefaultHasher::new();
-        repo.hash(&mut hasher);
-        let digest = to_hex(hasher.finish());
-        self.repos_dir().join(digest)
+    /// Lock the store for use, shared with other processes using it.
+    ///
+    /// Repos and hook environments are locked on their own while they are created, this
+    /// only keeps layout changes from happening under other processes.
+    pub(crate) async fn lock_shared_async(&self) -> Result<LockedFile, std::io::Error> {
+        LockedFile::acquire_shared(self.path.join(".lock"), "store").await
+    }
+
+    /// Lock a repo while it's cloned.
+    async fn lock_repo(
+        &self,
+        repo: &RemoteRepo,
+        on_wait: impl FnOnce(),
+    ) -> Result<ResourceLock, std::io::Error> {
+        let key = digest(&[repo.repo.as_str(), &repo.rev]);
+        self.lock_resource(&format!("repo-{key}"), format!("repo `{repo}`"), on_wait)
+            .await
+    }
+
+    /// Lock the environment of a hook while it's installed, so that processes installing
+    /// hooks with the same language and dependencies don't install them twice.
+    pub(crate) async fn lock_env(
+        &self,
+        hook: &Hook,
+        on_wait: impl FnOnce(),
+    ) -> Result<ResourceLock, std::io::Error> {
+        let mut parts = vec![hook.language.as_str()];
+        parts.extend(
+            hook.dependencies()
+                .iter()
+                .map(String::as_str)
+                .sorted_unstable(),
+        );
+        let key = digest(&parts);
+        self.lock_resource(
+            &format!("env-{key}"),
+            format!("{} environment of `{hook}`", hook.language.as_str()),
+            on_wait,
+        )
+        .await
+    }
+
+    /// Lock a resource in the store, calling `on_wait` if another process holds the lock.
+    async fn lock_resource(
+        &self,
+        name: &str,
+        resource: String,
+        on_wait: impl FnOnce(),
+    ) -> Result<ResourceLock, std::io::Error> {
+        let locks = self.path.join("locks");
+        fs_err::tokio::create_dir_all(&locks).await?;
+        let path = locks.join(format!("{name}.lock"));
+
+        // Tasks of this process wait for each other first, so the file lock can only be held
+        // by another process.
+        let task_lock = Arc::clone(TASK_LOCKS.lock().unwrap().entry(path.clone()).or_default());
+        let task = task_lock.lock_owned().await;
+
+        let file = if let Some(file) = LockedFile::try_acquire(&path, &resource)? {
+            file
+        } else {
+            on_wait();
+            LockedFile::acquire(path, resource).await?
+        };
+        Ok(ResourceLock {
+            _file: file,
+            _task: task,
+        })
+    }
+
+    /// Returns the path to the clone of a repo at a commit.
+    fn repo_path(&self, url: &str, commit: &str) -> PathBuf {
+        self.repos_dir().join(digest(&[url, commit]))
+    }
+
+    /// Returns the path to the file recording the commit a rev of a repo resolved to.
+    pub(crate) fn rev_path(&self, url: &str, rev: &str) -> PathBuf {
+        self.path.join("refs").join(digest(&[url, rev]))
     }
 
     pub(crate) fn repos_dir(&self) -> PathBuf {
@@ -172,6 +560,8 @@ pub(crate) enum ToolBucket {
 }
 
 impl ToolBucket {
+    pub(crate) const ALL: [Self; 4] = [Self::Uv, Self::Python, Self::Node, Self::Go];
+
     pub(crate) fn as_str(&self) -> &str {
         match self {
             ToolBucket::Uv => "uv",
@@ -189,6 +579,8 @@ pub(crate) enum CacheBucket {
 }
 
 impl CacheBucket {
+    pub(crate) const ALL: [Self; 2] = [Self::Uv, Self::Go];
+
     pub(crate) fn as_str(&self) -> &str {
         match self {
             CacheBucket::Uv => "uv",
@@ -197,7 +589,192 @@ impl CacheBucket {
     }
 }
 
-/// Convert a u64 to a hex string.
-fn to_hex(num: u64) -> String {
-    hex::encode(num.to_le_bytes())
+/// The metadata of a cloned repo, stored in `.prek-repo.json`.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RepoInfo {
+    pub(crate) repo: String,
+    pub(crate) commit: String,
+}
+
+/// Whether the rev is a full commit SHA, as printed by git.
+fn is_commit(rev: &str) -> bool {
+    matches!(rev.len(), 40 | 64) && rev.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// A stable digest of the parts, used as the name of store entries.
+fn digest(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update(b"\0");
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Files larger than this are not rewritten by [`relocate`], they are data rather than
+/// scripts or config files.
+const RELOCATE_MAX_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Whether the file is a text file that contains `needle`, reading it in chunks.
+///
+/// Like git, a file with a NUL byte in its first 8 KiB is binary.
+fn contains_in_text(path: &Path, needle: &[u8]) -> std::io::Result<bool> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+    const BINARY_CHECK_SIZE: usize = 8 * 1024;
+
+    let mut file = fs_err::File::open(path)?;
+    let mut buffer = vec![0; CHUNK_SIZE + needle.len()];
+    // Bytes kept from the previous chunk, so a match across chunks is found.
+    let mut kept = 0;
+    let mut first = true;
+    loop {
+        let read = match std::io::Read::read(&mut file, &mut buffer[kept..]) {
+            Ok(0) => return Ok(false),
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        let filled = kept + read;
+        if first {
+            first = false;
+            if buffer[..filled.min(BINARY_CHECK_SIZE)].contains(&0) {
+                return Ok(false);
+            }
+        }
+        if buffer[..filled].contains_str(needle) {
+            return Ok(true);
+        }
+        kept = filled.min(needle.len().saturating_sub(1));
+        buffer.copy_within(filled - kept..filled, 0);
+    }
+}
+
+/// Replace the path `from` with `to` in the symlinks and install info under `path`,
+/// and in text files if `rewrite_files` is set.
+///
+/// Used when an environment moves out of the staging directory, and when a bundle is
+/// imported into a store at another path.
+pub(crate) fn relocate(path: &Path, from: &Path, to: &Path, rewrite_files: bool) -> Result<()> {
+    let rebase =
+        |path: &Path| -> Option<PathBuf> { path.strip_prefix(from).ok().map(|rest| to.join(rest)) };
+
+    let metadata = fs_err::symlink_metadata(path)?;
+    if metadata.is_symlink() {
+        #[cfg(unix)]
+        if let Some(target) = rebase(&fs_err::read_link(path)?) {
+            trace!(path = %path.display(), target = %target.display(), "Relocating symlink");
+            fs_err::remove_file(path)?;
+            fs_err::os::unix::fs::symlink(target, path)?;
+        }
+    } else if metadata.is_dir() {
+        for entry in fs_err::read_dir(path)? {
+            relocate(&entry?.path(), from, to, rewrite_files)?;
+        }
+    } else if path
+        .file_name()
+        .is_some_and(|name| name == ".prek-hook.json")
+    {
+        let mut info: InstallInfo = serde_json::from_slice(&fs_err::read(path)?)?;
+        if let Some(env_path) = rebase(&info.env_path) {
+            info.env_path = env_path;
+        }
+        if let Some(toolchain) = rebase(&info.toolchain) {
+            info.toolchain = toolchain;
+        }
+        fs_err::write(path, serde_json::to_string_pretty(&info)?)?;
+    } else if rewrite_files && metadata.len() <= RELOCATE_MAX_SIZE {
+        let from = from.to_string_lossy();
+        if contains_in_text(path, from.as_bytes())? {
+            trace!(path = %path.display(), "Relocating file");
+            let content = fs_err::read(path)?;
+            let content = content.replace(from.as_bytes(), to.to_string_lossy().as_bytes());
+            fs_err::write(path, content)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use tokio::sync::Notify;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn lock_resource_same_process() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let store = Store::from_path(temp_dir.path());
+        let not_waiting = || panic!("no other process holds the lock");
+
+        let first = store
+            .lock_resource("env-test", "test".to_string(), not_waiting)
+            .await?;
+        let released = AtomicBool::new(false);
+        let release = async {
+            tokio::task::yield_now().await;
+            released.store(true, Ordering::SeqCst);
+            drop(first);
+        };
+        let second = store.lock_resource("env-test", "test".to_string(), not_waiting);
+
+        let ((), second) = tokio::join!(release, second);
+        second?;
+        assert!(released.load(Ordering::SeqCst));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lock_resource_other_process() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let store = Store::from_path(temp_dir.path());
+
+        // Another process holding the lock.
+        fs_err::create_dir_all(temp_dir.path().join("locks"))?;
+        let file = fs_err::File::create(temp_dir.path().join("locks").join("env-test.lock"))?;
+        file.file().lock()?;
+
+        let waiting = Notify::new();
+        let lock = store.lock_resource("env-test", "test".to_string(), || {
+            waiting.notify_one();
+        });
+        let release = async {
+            waiting.notified().await;
+            file.file().unlock()
+        };
+
+        let (lock, released) = tokio::join!(lock, release);
+        released?;
+        lock?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn contains_in_text_chunks() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("file");
+        let needle = b"/store/hooks/.tmp/node-abc";
+
+        // A match across the boundary of two chunks.
+        let mut content = vec![b'a'; 64 * 1024 - 10];
+        content.extend_from_slice(needle);
+        fs_err::write(&path, &content)?;
+        assert!(contains_in_text(&path, needle)?);
+
+        content.truncate(64 * 1024 - 10);
+        fs_err::write(&path, &content)?;
+        assert!(!contains_in_text(&path, needle)?);
+
+        // Binaries are not rewritten.
+        let mut content = b"\x7fELF\0".to_vec();
+        content.extend_from_slice(needle);
+        fs_err::write(&path, &content)?;
+        assert!(!contains_in_text(&path, needle)?);
+
+        Ok(())
+    }
 }
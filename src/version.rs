@@ -23,8 +23,10 @@ SOFTWARE.
 
 // See also <https://github.com/astral-sh/ruff/blob/8118d29419055b779719cc96cdf3dacb29ac47c9/crates/ruff/src/version.rs>
 use std::fmt;
+use std::sync::LazyLock;
 
 use serde::Serialize;
+use thiserror::Error;
 
 /// Information about the git repository where prek was built from.
 #[derive(Serialize)]
@@ -97,3 +99,129 @@ pub fn version() -> VersionInfo {
         commit_info,
     }
 }
+
+/// The pre-commit version whose config and manifest formats prek is compatible with.
+const PRE_COMMIT_COMPATIBLE_VERSION: &str = "4.2.0";
+
+#[derive(Debug, Error)]
+pub enum MinimumVersionError {
+    #[error("Invalid `{key}` `{value}`")]
+    Invalid { key: &'static str, value: String },
+
+    #[error(
+        "Required minimum pre-commit version `{0}` is greater than the version prek is compatible with (`{PRE_COMMIT_COMPATIBLE_VERSION}`), please consider updating prek by running `prek self update`"
+    )]
+    PreCommit(String),
+
+    #[error(
+        "Required minimum prek version `{0}` is greater than current version `{current}`, please consider updating prek by running `prek self update`",
+        current = env!("CARGO_PKG_VERSION")
+    )]
+    Prek(String),
+}
+
+impl MinimumVersionError {
+    /// The config key that requires the version.
+    pub(crate) fn key(&self) -> &'static str {
+        match self {
+            Self::Invalid { key, .. } => key,
+            Self::PreCommit(_) => "minimum_pre_commit_version",
+            Self::Prek(_) => "minimum_prek_version",
+        }
+    }
+
+    /// A short description of the error, to label the key with.
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            Self::Invalid { .. } => "invalid version",
+            Self::PreCommit(_) | Self::Prek(_) => "required here",
+        }
+    }
+}
+
+/// Parse a version like `3`, `3.2` or `3.2.1`, missing components are zero.
+fn parse_version(version: &str) -> Option<semver::Version> {
+    let mut parts = version.trim().splitn(3, '.');
+    let mut next = || parts.next().map_or(Ok(0), str::parse::<u64>).ok();
+    let (major, minor, patch) = (next()?, next()?, next()?);
+    Some(semver::Version::new(major, minor, patch))
+}
+
+/// The higher of two minimum versions, `version` if either is invalid.
+pub(crate) fn max_minimum_version(version: String, other: String) -> String {
+    match (parse_version(&version), parse_version(&other)) {
+        (Some(a), Some(b)) if b > a => other,
+        _ => version,
+    }
+}
+
+/// The version of this prek build, which may be a pre-release like `0.2.0-alpha.1`.
+static PREK_VERSION: LazyLock<semver::Version> =
+    LazyLock::new(|| semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("valid version"));
+
+/// Check the `minimum_pre_commit_version` and `minimum_prek_version` of a config or hook.
+pub(crate) fn check_minimum_versions(
+    pre_commit: Option<&str>,
+    prek: Option<&str>,
+) -> Result<(), MinimumVersionError> {
+    if let Some(required) = pre_commit {
+        let version = parse_version(required).ok_or_else(|| MinimumVersionError::Invalid {
+            key: "minimum_pre_commit_version",
+            value: required.to_string(),
+        })?;
+        if version > parse_version(PRE_COMMIT_COMPATIBLE_VERSION).expect("valid version") {
+            return Err(MinimumVersionError::PreCommit(required.to_string()));
+        }
+    }
+
+    if let Some(required) = prek {
+        let version = parse_version(required).ok_or_else(|| MinimumVersionError::Invalid {
+            key: "minimum_prek_version",
+            value: required.to_string(),
+        })?;
+        if version > *PREK_VERSION {
+            return Err(MinimumVersionError::Prek(required.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimum_versions() {
+        assert!(check_minimum_versions(Some("4.2"), Some("0.0.1")).is_ok());
+        assert!(matches!(
+            check_minimum_versions(Some("99.0"), None),
+            Err(MinimumVersionError::PreCommit(_))
+        ));
+        assert!(matches!(
+            check_minimum_versions(None, Some("99.0.0")),
+            Err(MinimumVersionError::Prek(_))
+        ));
+        assert!(matches!(
+            check_minimum_versions(None, Some("1.x")),
+            Err(MinimumVersionError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn pre_release_version() {
+        let version = semver::Version::parse("0.2.0-alpha.1").unwrap();
+        assert!(parse_version("0.2").unwrap() > version);
+        assert!(parse_version("0.1.9").unwrap() < version);
+        // The version of this build parses, pre-release or not.
+        assert!(parse_version("0").unwrap() <= *PREK_VERSION);
+    }
+
+    #[test]
+    fn max_minimum_versions() {
+        let max = |a: &str, b: &str| max_minimum_version(a.to_string(), b.to_string());
+        assert_eq!(max("0.2", "0.10.0"), "0.10.0");
+        assert_eq!(max("1.0.1", "1"), "1.0.1");
+        assert_eq!(max("1.x", "2.0"), "1.x");
+    }
+}
@@ -5,16 +5,20 @@ use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
 use futures::StreamExt;
-use itertools::zip_eq;
+use itertools::{Itertools, zip_eq};
 use rustc_hash::FxHashMap;
 use thiserror::Error;
 use tracing::{debug, error};
 
-use crate::config::{self, ALTER_CONFIG_FILE, CONFIG_FILE, Config, ManifestHook, read_config};
+use crate::config::{
+    self, CONFIG_FILE, CONFIG_FILES, Config, ConfigBase, ManifestHook, RemoteRepo, UnknownKeys,
+    read_config,
+};
 use crate::fs::{CWD, Simplified};
 use crate::hook::{self, Hook, HookBuilder, Repo};
+use crate::settings::Settings;
 use crate::store::Store;
-use crate::{store, warn_user};
+use crate::{git, offline, store, warn_user};
 
 #[derive(Error, Debug)]
 pub(crate) enum Error {
@@ -25,7 +29,23 @@ pub(crate) enum Error {
     Hook(#[from] hook::Error),
 
     #[error("Hook `{hook}` not present in repo `{repo}`")]
-    HookNotFound { hook: String, repo: String },
+    HookNotFound {
+        hook: String,
+        repo: String,
+        config: PathBuf,
+    },
+
+    #[error(transparent)]
+    Git(#[from] git::Error),
+
+    #[error("No project found in directory `{0}`")]
+    ProjectNotFound(String),
+
+    #[error("`--cd` can't be used with `--config` `{0}`, which is not in the root of the repo")]
+    CdWithConfig(String),
+
+    #[error("Configs extend each other in a cycle: {0}")]
+    ExtendsCycle(String),
 
     #[error("Failed to initialize repo `{repo}`")]
     Store {
@@ -33,17 +53,26 @@ pub(crate) enum Error {
         #[source]
         error: Box<store::Error>,
     },
+
+    #[error(transparent)]
+    Offline(#[from] offline::Missing),
 }
 
 pub(crate) trait HookInitReporter {
     fn on_clone_start(&self, repo: &str) -> usize;
+    /// Another process holds the lock of the repo being cloned.
+    fn on_clone_wait(&self, id: usize, repo: &str);
     fn on_clone_complete(&self, id: usize);
     fn on_complete(&self);
 }
 
 pub(crate) struct Project {
     config_path: PathBuf,
+    // The directory of the project, relative to the workspace root.
+    relative_path: PathBuf,
     config: Config,
+    // The files hooks inherited from base configs or the global settings are defined in.
+    hook_sources: FxHashMap<String, PathBuf>,
     repos: Vec<Arc<Repo>>,
 }
 
@@ -59,21 +88,20 @@ impl Project {
             )));
         }
 
-        let main = CWD.join(CONFIG_FILE);
-        let alternate = CWD.join(ALTER_CONFIG_FILE);
-        if main.exists() && alternate.exists() {
-            warn_user!(
-                "Both {main} and {alternate} exist, using {main}",
-                main = main.display(),
-                alternate = alternate.display()
-            );
-        }
-        if main.exists() {
+        let mut found = CONFIG_FILES
+            .iter()
+            .map(|file| CWD.join(file))
+            .filter(|path| path.exists());
+        if let Some(main) = found.next() {
+            for alternate in found {
+                warn_user!(
+                    "Both {main} and {alternate} exist, using {main}",
+                    main = main.display(),
+                    alternate = alternate.display()
+                );
+            }
             return Ok(main);
         }
-        if alternate.exists() {
-            return Ok(alternate);
-        }
 
         Err(Error::InvalidConfig(config::Error::NotFound(
             CONFIG_FILE.into(),
@@ -81,22 +109,67 @@ impl Project {
     }
 
     /// Initialize a new project from the configuration file or the file in the current working directory.
-    pub(crate) fn from_config_file(config: Option<PathBuf>) -> Result<Self, Error> {
+    pub(crate) async fn from_config_file(
+        config: Option<PathBuf>,
+        store: &Store,
+        reporter: Option<&dyn HookInitReporter>,
+    ) -> Result<Self, Error> {
         let config_path = Self::find_config_file(config)?;
-        Self::new(config_path)
+        let project = Self::new(config_path, store, reporter).await;
+        reporter.map(HookInitReporter::on_complete);
+        project
     }
 
     /// Initialize a new project from the configuration file.
-    pub(crate) fn new(config_path: PathBuf) -> Result<Self, Error> {
+    ///
+    /// The base configs in `extends` and the global settings are merged into the config,
+    /// cloning remote base configs into the store.
+    pub(crate) async fn new(
+        config_path: PathBuf,
+        store: &Store,
+        reporter: Option<&dyn HookInitReporter>,
+    ) -> Result<Self, Error> {
         debug!(
             path = %config_path.display(),
             "Loading project configuration"
         );
-        let config = read_config(&config_path)?;
+        let mut config = read_config(&config_path)?;
+        let own_hooks = hook_ids(&config);
+        let mut hook_sources = FxHashMap::default();
+        if config.extends.is_some() {
+            let canonical =
+                dunce::canonicalize(&config_path).unwrap_or_else(|_| config_path.clone());
+            let base_dir = canonical.parent().unwrap_or(Path::new("")).to_path_buf();
+            let mut chain = vec![canonical];
+            config = resolve_extends(
+                config,
+                &base_dir,
+                store,
+                reporter,
+                &mut chain,
+                &mut hook_sources,
+            )
+            .await?;
+        }
+        // Hooks from the global settings run in every project, unless redefined by id.
+        if let Some(global) = Settings::get().base_config() {
+            if let Some(path) = Settings::path() {
+                for id in hook_ids(&global) {
+                    hook_sources.entry(id).or_insert_with(|| path.clone());
+                }
+            }
+            config.inherit(global);
+        }
+        for id in own_hooks {
+            hook_sources.remove(&id);
+        }
+
         let size = config.repos.len();
         Ok(Self {
             config,
             config_path,
+            relative_path: PathBuf::new(),
+            hook_sources,
             repos: Vec::with_capacity(size),
         })
     }
@@ -105,6 +178,13 @@ impl Project {
         &self.config
     }
 
+    /// The directory of the project, relative to the workspace root.
+    ///
+    /// It's empty for the project at the workspace root.
+    pub(crate) fn relative_path(&self) -> &Path {
+        &self.relative_path
+    }
+
     pub(crate) fn config_file(&self) -> &Path {
         &self.config_path
     }
@@ -118,12 +198,30 @@ impl Project {
         let mut seen = HashSet::new();
 
         // Prepare remote repos in parallel.
-        let remotes_iter = self.config.repos.iter().filter_map(|repo| match repo {
-            // Deduplicate remote repos.
-            config::Repo::Remote(repo) if seen.insert(repo) => Some(repo),
-            _ => None,
-        });
-        let mut tasks = futures::stream::iter(remotes_iter)
+        let remotes = self
+            .config
+            .repos
+            .iter()
+            .filter_map(|repo| match repo {
+                // Deduplicate remote repos.
+                config::Repo::Remote(repo) if seen.insert(repo) => Some(repo),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Report all the missing repos at once, instead of failing on the first one.
+        if offline::is_enabled() {
+            let missing = remotes
+                .iter()
+                .filter(|repo| !store.is_cloned(repo))
+                .map(|repo| format!("repo `{repo}`"))
+                .collect::<offline::Missing>();
+            if !missing.is_empty() {
+                return Err(missing.into());
+            }
+        }
+
+        let mut tasks = futures::stream::iter(remotes)
             .map(async |repo_config| {
                 let remote_repos = remote_repos.clone();
 
@@ -131,7 +229,11 @@ impl Project {
                     .map(|reporter| (reporter, reporter.on_clone_start(&format!("{repo_config}"))));
 
                 let path = store
-                    .clone_repo(repo_config)
+                    .clone_repo(repo_config, || {
+                        if let Some((reporter, progress)) = progress {
+                            reporter.on_clone_wait(progress, &repo_config.to_string());
+                        }
+                    })
                     .await
                     .map_err(|e| Error::Store {
                         repo: format!("{}", repo_config.repo),
@@ -184,6 +286,14 @@ impl Project {
         Ok(())
     }
 
+    /// The config file the hook is defined in.
+    fn hook_config_path(&self, id: &str) -> PathBuf {
+        self.hook_sources
+            .get(id)
+            .unwrap_or(&self.config_path)
+            .clone()
+    }
+
     /// Load and prepare hooks for the project.
     pub(crate) async fn init_hooks(
         &mut self,
@@ -203,11 +313,18 @@ impl Project {
                             return Err(Error::HookNotFound {
                                 hook: hook_config.id.clone(),
                                 repo: repo.to_string(),
+                                config: self.hook_config_path(&hook_config.id),
                             });
                         };
 
                         let repo = Arc::clone(repo);
-                        let mut builder = HookBuilder::new(repo, hook.clone(), hooks.len());
+                        let mut builder = HookBuilder::new(
+                            repo,
+                            hook.clone(),
+                            hooks.len(),
+                            self.relative_path.clone(),
+                            self.hook_config_path(&hook_config.id),
+                        );
                         builder.update(hook_config);
                         builder.combine(&self.config);
 
@@ -218,7 +335,13 @@ impl Project {
                 config::Repo::Local(repo_config) => {
                     for hook_config in &repo_config.hooks {
                         let repo = Arc::clone(repo);
-                        let mut builder = HookBuilder::new(repo, hook_config.clone(), hooks.len());
+                        let mut builder = HookBuilder::new(
+                            repo,
+                            hook_config.clone(),
+                            hooks.len(),
+                            self.relative_path.clone(),
+                            self.hook_config_path(&hook_config.id),
+                        );
                         builder.combine(&self.config);
 
                         let hook = builder.build()?;
@@ -228,8 +351,15 @@ impl Project {
                 config::Repo::Meta(repo_config) => {
                     for hook_config in &repo_config.hooks {
                         let repo = Arc::clone(repo);
+                        let config_path = self.hook_config_path(&hook_config.0.id);
                         let hook_config = ManifestHook::from(hook_config.clone());
-                        let mut builder = HookBuilder::new(repo, hook_config, hooks.len());
+                        let mut builder = HookBuilder::new(
+                            repo,
+                            hook_config,
+                            hooks.len(),
+                            self.relative_path.clone(),
+                            config_path,
+                        );
                         builder.combine(&self.config);
 
                         let hook = builder.build()?;
@@ -244,3 +374,297 @@ impl Project {
         Ok(hooks)
     }
 }
+
+/// Merge the base configs in `extends` into the config, recursively.
+///
+/// `chain` holds the configs being resolved, to detect cycles. The file each inherited hook
+/// is defined in is recorded in `hook_sources`.
+async fn resolve_extends(
+    mut config: Config,
+    base_dir: &Path,
+    store: &Store,
+    reporter: Option<&dyn HookInitReporter>,
+    chain: &mut Vec<PathBuf>,
+    hook_sources: &mut FxHashMap<String, PathBuf>,
+) -> Result<Config, Error> {
+    let Some(extends) = config.extends.take() else {
+        return Ok(config);
+    };
+
+    let mut merged: Option<Config> = None;
+    for base in extends {
+        let path = match &base {
+            ConfigBase::Local(path) => base_dir.join(path),
+            ConfigBase::Remote { repo, rev, path } => {
+                let repo_config = RemoteRepo {
+                    repo: repo.clone(),
+                    rev: rev.clone(),
+                    hooks: vec![],
+                    unknown: UnknownKeys::default(),
+                };
+                let progress = reporter
+                    .map(|reporter| (reporter, reporter.on_clone_start(&format!("{repo_config}"))));
+
+                // Base configs are cached in the store like cloned repos.
+                let repo_path = store
+                    .clone_repo(&repo_config, || {
+                        if let Some((reporter, progress)) = progress {
+                            reporter.on_clone_wait(progress, &repo_config.to_string());
+                        }
+                    })
+                    .await
+                    .map_err(|e| Error::Store {
+                        repo: repo.to_string(),
+                        error: Box::new(e),
+                    })?;
+
+                if let Some((reporter, progress)) = progress {
+                    reporter.on_clone_complete(progress);
+                }
+                repo_path.join(path)
+            }
+        };
+
+        let path = dunce::canonicalize(&path).unwrap_or(path);
+        if let Some(start) = chain.iter().position(|p| *p == path) {
+            let cycle = chain[start..]
+                .iter()
+                .chain([&path])
+                .map(|path| format!("`{}`", path.user_display()))
+                .join(" -> ");
+            return Err(Error::ExtendsCycle(cycle));
+        }
+        debug!(path = %path.display(), "Loading base config");
+
+        let base_config = read_config(&path)?;
+        let base_hooks = hook_ids(&base_config);
+        chain.push(path);
+        let base_dir = chain
+            .last()
+            .and_then(|path| path.parent())
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        let base_config = Box::pin(resolve_extends(
+            base_config,
+            &base_dir,
+            store,
+            reporter,
+            chain,
+            hook_sources,
+        ))
+        .await?;
+        let path = chain.pop().expect("pushed above");
+        // Hooks of the base take precedence over the ones it inherits.
+        for id in base_hooks {
+            hook_sources.insert(id, path.clone());
+        }
+
+        // Later bases take precedence over earlier ones.
+        merged = Some(match merged {
+            None => base_config,
+            Some(earlier) => {
+                let mut later = base_config;
+                later.inherit(earlier);
+                later
+            }
+        });
+    }
+
+    if let Some(base) = merged {
+        config.inherit(base);
+    }
+    Ok(config)
+}
+
+/// Whether the config file is in the root of the repo, the current working directory.
+fn is_root_config(config_path: &Path) -> bool {
+    let dir = CWD.join(config_path);
+    let Some(dir) = dir.parent() else {
+        return false;
+    };
+    match (dunce::canonicalize(dir), dunce::canonicalize(&*CWD)) {
+        (Ok(dir), Ok(cwd)) => dir == cwd,
+        _ => false,
+    }
+}
+
+/// Ids of the hooks configured in the config.
+fn hook_ids(config: &Config) -> Vec<String> {
+    config
+        .repos
+        .iter()
+        .flat_map(config::Repo::hook_ids)
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// A workspace is a git repository with one or more projects.
+///
+/// Every directory with a config file is a project, the hooks of a project run in its directory
+/// on the files under it that don't belong to a nested project.
+pub(crate) struct Workspace {
+    projects: Vec<Project>,
+    // Directories of all the projects in the workspace, including the ones not selected.
+    project_dirs: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// Discover the projects in the workspace, must be called from the workspace root.
+    ///
+    /// If a config file is given, it's the only project in the workspace. Otherwise, every
+    /// config file tracked by git defines a project. With `cd`, only the project in that
+    /// directory is selected.
+    pub(crate) async fn discover(
+        config: Option<PathBuf>,
+        cd: Option<&Path>,
+        store: &Store,
+        reporter: Option<&dyn HookInitReporter>,
+    ) -> Result<Self, Error> {
+        let workspace = Self::discover_projects(config, cd, store, reporter).await;
+        reporter.map(HookInitReporter::on_complete);
+        workspace
+    }
+
+    async fn discover_projects(
+        config: Option<PathBuf>,
+        cd: Option<&Path>,
+        store: &Store,
+        reporter: Option<&dyn HookInitReporter>,
+    ) -> Result<Self, Error> {
+        let root = if let Some(config) = config {
+            let config_path = Project::find_config_file(Some(config))?;
+            // A config outside the root of the repo is a project of its own, the config in the
+            // root, like the one passed by the git hook scripts, is the root of the workspace.
+            if !is_root_config(&config_path) {
+                if cd.is_some() {
+                    return Err(Error::CdWithConfig(config_path.user_display().to_string()));
+                }
+                let project = Project::new(config_path, store, reporter).await?;
+                return Self::select(vec![project], None);
+            }
+            Some(Project::new(config_path, store, reporter).await?)
+        } else {
+            match Project::find_config_file(None) {
+                Ok(config_path) => Some(Project::new(config_path, store, reporter).await?),
+                Err(Error::InvalidConfig(config::Error::NotFound(_))) => None,
+                Err(err) => return Err(err),
+            }
+        };
+
+        let mut config_files: Vec<PathBuf> = git::git_ls_files(None)
+            .await?
+            .into_iter()
+            .map(PathBuf::from)
+            .filter(|path| {
+                path.parent().is_some_and(|dir| !dir.as_os_str().is_empty())
+                    && path
+                        .file_name()
+                        .is_some_and(|name| CONFIG_FILES.iter().any(|file| name == *file))
+            })
+            .collect();
+        // Sort config files in the same directory by precedence.
+        let precedence = |path: &Path| {
+            CONFIG_FILES
+                .iter()
+                .position(|file| path.file_name() == Some(file.as_ref()))
+        };
+        config_files.sort_by(|a, b| {
+            a.parent()
+                .cmp(&b.parent())
+                .then_with(|| precedence(a).cmp(&precedence(b)))
+        });
+
+        let mut projects: Vec<Project> = root.into_iter().collect();
+        for config_file in config_files {
+            let relative_path = config_file.parent().unwrap_or(Path::new("")).to_path_buf();
+            if projects.iter().any(|p| p.relative_path == relative_path) {
+                warn_user!(
+                    "Multiple config files found in `{}`, using `{}`",
+                    relative_path.display(),
+                    projects
+                        .iter()
+                        .find(|p| p.relative_path == relative_path)
+                        .unwrap()
+                        .config_path
+                        .user_display()
+                );
+                continue;
+            }
+
+            let mut project = Project::new(CWD.join(&config_file), store, reporter).await?;
+            project.relative_path = relative_path;
+            projects.push(project);
+        }
+
+        if projects.is_empty() {
+            return Err(Error::InvalidConfig(config::Error::NotFound(
+                CONFIG_FILE.into(),
+            )));
+        }
+
+        Self::select(projects, cd)
+    }
+
+    fn select(projects: Vec<Project>, cd: Option<&Path>) -> Result<Self, Error> {
+        let project_dirs = projects.iter().map(|p| p.relative_path.clone()).collect();
+
+        let projects = if let Some(cd) = cd {
+            // Compare the directory without `./` or a trailing slash.
+            let cd: PathBuf = cd.components().collect();
+            let project = projects
+                .into_iter()
+                .find(|p| p.relative_path == cd)
+                .ok_or_else(|| Error::ProjectNotFound(cd.to_string_lossy().to_string()))?;
+            vec![project]
+        } else {
+            projects
+        };
+
+        debug!(
+            "Projects in the workspace: {:?}",
+            projects
+                .iter()
+                .map(|p| &p.relative_path)
+                .collect::<Vec<_>>()
+        );
+
+        Ok(Self {
+            projects,
+            project_dirs,
+        })
+    }
+
+    pub(crate) fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    pub(crate) fn projects_mut(&mut self) -> &mut [Project] {
+        &mut self.projects
+    }
+
+    /// Route each file to the nearest project that contains it.
+    ///
+    /// Returns the filenames of each selected project, relative to the project directory.
+    pub(crate) fn route_files(&self, filenames: &[String]) -> Vec<Vec<String>> {
+        let mut routed = vec![Vec::new(); self.projects.len()];
+
+        for filename in filenames {
+            let path = Path::new(filename);
+            let Some(dir) = self
+                .project_dirs
+                .iter()
+                .filter(|dir| path.starts_with(dir))
+                .max_by_key(|dir| dir.components().count())
+            else {
+                continue;
+            };
+            let Some(idx) = self.projects.iter().position(|p| p.relative_path == *dir) else {
+                continue;
+            };
+            let relative = path.strip_prefix(dir).expect("project contains the file");
+            routed[idx].push(relative.to_string_lossy().to_string());
+        }
+
+        routed
+    }
+}
@@ -0,0 +1,102 @@
+use assert_fs::fixture::PathChild;
+use constants::env_vars::EnvVars;
+
+#[cfg(unix)]
+use crate::common::GREETER_HOOK;
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+/// Export the repos, environments and toolchains of a config into a bundle, and run the
+/// hooks offline from another store.
+#[cfg(unix)]
+#[test]
+fn export_and_import() -> anyhow::Result<()> {
+    let hook_repo = TestContext::new();
+    let repo_url = hook_repo.init_hook_repo(indoc::indoc! {r"
+        - id: hello
+          name: hello
+          language: system
+          entry: echo hello
+          always_run: true
+    "});
+
+    let context = TestContext::new();
+    context.init_project();
+    let mirror = context.node_mirror();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo_url}
+            rev: v1.0.0
+            hooks:
+              - id: hello
+          - repo: local
+            hooks:
+              - {GREETER_HOOK}
+    "});
+    context.git_add(".pre-commit-config.yaml");
+
+    let mut export = context.bundle();
+    export
+        .arg("export")
+        .arg("hooks.tar.zst")
+        .env(EnvVars::PREK_NODE_MIRROR, &mirror);
+    cmd_snapshot!(context.filters(), export, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Exported 4 entries to `hooks.tar.zst`
+
+    ----- stderr -----
+    ");
+
+    // Import into an empty store.
+    let other = TestContext::new();
+    let bundle = context.work_dir().child("hooks.tar.zst");
+    cmd_snapshot!(other.filters(), other.bundle().arg("import").arg(&*bundle), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Imported 4 entries into `[HOME]/`
+
+    ----- stderr -----
+    ");
+
+    // Nothing may point back into the store the bundle was exported from.
+    fs_err::remove_dir_all(context.home_dir().path())?;
+
+    let mut run = context.run();
+    run.arg("--offline")
+        .env(EnvVars::PREK_HOME, &**other.home_dir());
+    cmd_snapshot!(context.filters(), run, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hello....................................................................Passed
+    greeter..................................................................Passed
+    - hook id: greeter
+    - duration: [TIME]
+      greeter says hello
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// Bundles are always `.tar.zst` archives.
+#[test]
+fn export_extension() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config("repos: []\n");
+
+    cmd_snapshot!(context.filters(), context.bundle().arg("export").arg("hooks.zip"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Bundle file `hooks.zip` must have the `.tar.zst` extension
+    ");
+}
@@ -0,0 +1,217 @@
+use std::process::Command;
+
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::fixture::{FileWriteStr, PathChild};
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+/// Adopt the repos cloned by pre-commit, then run the hooks offline from them.
+#[test]
+fn import_pre_commit() -> anyhow::Result<()> {
+    let hook_repo = TestContext::new();
+    let repo_url = hook_repo.init_hook_repo(indoc::indoc! {r"
+        - id: hello
+          name: hello
+          language: system
+          entry: echo hello
+          always_run: true
+    "});
+
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo_url}
+            rev: v1.0.0
+            hooks:
+              - id: hello
+    "});
+    context.git_add(".pre-commit-config.yaml");
+
+    // A pre-commit cache with a clone of the repo, and an environment installed into it.
+    let pre_commit_home = context.work_dir().child("pre-commit");
+    let clone = pre_commit_home.child("repo1234");
+    Command::new("git")
+        .arg("clone")
+        .arg(&repo_url)
+        .arg(&*clone)
+        .assert()
+        .success();
+    clone
+        .child("py_env-python3")
+        .child("pyvenv.cfg")
+        .write_str("")?;
+
+    let conn = rusqlite::Connection::open(pre_commit_home.child("db.db"))?;
+    conn.execute_batch(
+        "CREATE TABLE repos (repo TEXT NOT NULL, ref TEXT NOT NULL, path TEXT NOT NULL, PRIMARY KEY (repo, ref));",
+    )?;
+    // pre-commit clones the repo again for hooks with `additional_dependencies`.
+    conn.execute(
+        "INSERT INTO repos (repo, ref, path) VALUES (?1, 'v1.0.0', ?2), (?3, 'v1.0.0', ?2), ('local', '1', '/nonexistent')",
+        [
+            repo_url.as_str(),
+            &clone.to_string_lossy(),
+            &format!("{repo_url}:left-pad,@types/node"),
+        ],
+    )?;
+    drop(conn);
+
+    cmd_snapshot!(context.filters(), context.cache().arg("import-pre-commit").arg("pre-commit"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Imported 1 repo from `pre-commit`
+
+    ----- stderr -----
+    ");
+
+    // Only the repo itself is imported.
+    let repos = context.home_dir().child("repos");
+    let imported = repos.read_dir()?.next().expect("repo is imported")?.path();
+    assert!(imported.join(".pre-commit-hooks.yaml").is_file());
+    assert!(!imported.join("py_env-python3").exists());
+
+    cmd_snapshot!(context.filters(), context.run().arg("--offline"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hello....................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    // Importing again is a no-op.
+    cmd_snapshot!(context.filters(), context.cache().arg("import-pre-commit").arg("pre-commit"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Imported 0 repos from `pre-commit`
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn cache_dir() {
+    let context = TestContext::new();
+
+    cmd_snapshot!(context.filters(), context.cache().arg("dir"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [HOME]/
+
+    ----- stderr -----
+    ");
+}
+
+/// List hook environments, and remove repos, environments and toolchains.
+#[test]
+fn list_and_remove() -> anyhow::Result<()> {
+    let hook_repo = TestContext::new();
+    let repo_url = hook_repo.init_hook_repo(indoc::indoc! {r"
+        - id: hello
+          name: hello
+          language: system
+          entry: echo hello
+          always_run: true
+    "});
+
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo_url}
+            rev: v1.0.0
+            hooks:
+              - id: hello
+    "});
+    context.git_add(".pre-commit-config.yaml");
+    context.run().assert().success();
+
+    cmd_snapshot!(context.filters(), context.cache().arg("list"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    No hook environments installed
+
+    ----- stderr -----
+    ");
+
+    // A hook environment and a toolchain.
+    let env = context.home_dir().child("hooks").child("python-abc123");
+    env.child(".prek-hook.json").write_str(&format!(
+        r#"{{
+            "language": "python",
+            "language_version": "3.12.1",
+            "dependencies": ["ruff", "black"],
+            "env_path": "{}",
+            "toolchain": "/usr/bin/python3",
+            "extra": {{}}
+        }}"#,
+        env.display()
+    ))?;
+    context
+        .home_dir()
+        .child("tools")
+        .child("node")
+        .child("22.11.0")
+        .child("bin")
+        .child("node")
+        .write_str("")?;
+
+    cmd_snapshot!(context.filters(), context.cache().arg("list"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    python-abc123 (python 3.12.1)
+      dependencies: black, ruff
+      path: [HOME]/hooks/python-abc123
+      last used: just now
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.cache().arg("size"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    repos       [SIZE]
+    hooks       [SIZE]
+    tools/node  [SIZE]
+    Total       [SIZE]
+
+    ----- stderr -----
+    ");
+
+    let mut filters = context.filters();
+    filters.push((r"[0-9a-f]{64}", "[HASH]"));
+    cmd_snapshot!(filters.clone(), context.cache().arg("remove").arg(&repo_url).arg("python-abc123").arg("node/22.11.0"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Removed `[HOME]/repos/[HASH]`
+    Removed `[HOME]/hooks/python-abc123`
+    Removed `[HOME]/tools/node/22.11.0`
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(filters, context.cache().arg("remove").arg("https://github.com/prek-test-repos/missing").arg("node/../..").arg("go"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No cached repo, hook environment or toolchain matches `https://github.com/prek-test-repos/missing`
+    error: No cached repo, hook environment or toolchain matches `node/../..`
+    error: No cached repo, hook environment or toolchain matches `go`
+    ");
+
+    Ok(())
+}
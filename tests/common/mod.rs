@@ -14,6 +14,7 @@ use constants::env_vars::EnvVars;
 pub struct TestContext {
     temp_dir: ChildPath,
     home_dir: ChildPath,
+    config_dir: ChildPath,
 
     /// Standard filters for this test context.
     filters: Vec<(String, String)>,
@@ -36,6 +37,9 @@ impl TestContext {
         let home_dir = ChildPath::new(root.path()).child("home");
         fs_err::create_dir_all(&home_dir).expect("Failed to create test home directory");
 
+        let config_dir = ChildPath::new(root.path()).child("config");
+        fs_err::create_dir_all(&config_dir).expect("Failed to create test config directory");
+
         let mut filters = Vec::new();
 
         filters.extend(
@@ -48,6 +52,11 @@ impl TestContext {
                 .into_iter()
                 .map(|pattern| (pattern, "[HOME]/".to_string())),
         );
+        filters.extend(
+            Self::path_patterns(&config_dir)
+                .into_iter()
+                .map(|pattern| (pattern, "[CONFIG_HOME]/".to_string())),
+        );
 
         let current_exe = assert_cmd::cargo::cargo_bin("prek");
         filters.extend(
@@ -59,6 +68,7 @@ impl TestContext {
         Self {
             temp_dir,
             home_dir,
+            config_dir,
             filters,
             _root: root,
         }
@@ -118,6 +128,7 @@ impl TestContext {
         let mut cmd = Command::new(bin);
         cmd.current_dir(self.work_dir());
         cmd.env(EnvVars::PREK_HOME, &**self.home_dir());
+        cmd.env(EnvVars::PREK_CONFIG_HOME, &**self.config_dir());
         cmd.env(EnvVars::PREK_INTERNAL__SORT_FILENAMES, "1");
         cmd
     }
@@ -170,6 +181,24 @@ impl TestContext {
         command
     }
 
+    pub fn cache(&self) -> Command {
+        let mut command = self.command();
+        command.arg("cache");
+        command
+    }
+
+    pub fn bundle(&self) -> Command {
+        let mut command = self.command();
+        command.arg("bundle");
+        command
+    }
+
+    pub fn doctor(&self) -> Command {
+        let mut command = self.command();
+        command.arg("doctor");
+        command
+    }
+
     /// Standard snapshot filters _plus_ those for this test context.
     pub fn filters(&self) -> Vec<(&str, &str)> {
         // Put test context snapshots before the default filters
@@ -191,6 +220,11 @@ impl TestContext {
         &self.home_dir
     }
 
+    /// The directory of the global `config.toml`.
+    pub fn config_dir(&self) -> &ChildPath {
+        &self.config_dir
+    }
+
     /// Initialize a sample project for prek.
     pub fn init_project(&self) {
         Command::new("git")
@@ -275,6 +309,28 @@ impl TestContext {
             .success();
     }
 
+    /// Initialize a hook repo with the given `.pre-commit-hooks.yaml`, tagged `v1.0.0`.
+    ///
+    /// Returns the URL of the repo.
+    pub fn init_hook_repo(&self, manifest: &str) -> String {
+        self.init_project();
+        self.configure_git_author();
+        self.temp_dir
+            .child(".pre-commit-hooks.yaml")
+            .write_str(manifest)
+            .expect("Failed to write hooks manifest");
+        self.git_add(".");
+        self.git_commit("Add hooks");
+        Command::new("git")
+            .arg("tag")
+            .arg("v1.0.0")
+            .current_dir(&self.temp_dir)
+            .assert()
+            .success();
+
+        format!("file://{}", self.temp_dir.display())
+    }
+
     /// Write a `.pre-commit-config.yaml` file in the temporary directory.
     pub fn write_pre_commit_config(&self, content: &str) {
         self.temp_dir
@@ -282,8 +338,112 @@ impl TestContext {
             .write_str(content)
             .expect("Failed to write pre-commit config");
     }
+
+    /// Write a config with a local `node` hook, run by the Node.js found on the system.
+    pub fn write_node_hook_config(&self) {
+        self.write_pre_commit_config(indoc::indoc! {r#"
+            repos:
+              - repo: local
+                hooks:
+                  - id: node
+                    name: node
+                    language: node
+                    entry: node -e "console.log('hello')"
+                    always_run: true
+                    pass_filenames: false
+        "#});
+    }
+
+    /// Lock the store shared, like another process using the store it has initialized.
+    ///
+    /// The lock is held until the returned file is dropped or unlocked.
+    pub fn lock_store_shared(&self) -> fs_err::File {
+        self.home_dir
+            .child(".version")
+            .write_str("1")
+            .expect("Failed to write store version");
+        let lock = fs_err::File::create(self.home_dir.child(".lock").path())
+            .expect("Failed to create store lock");
+        lock.file().lock_shared().expect("Failed to lock store");
+        lock
+    }
+
+    /// Serve a fake Node.js 99.0.0 release from a local mirror, returns its url.
+    ///
+    /// Its `npm` installs a package as a script in `bin` that runs the package by the absolute
+    /// path of the environment, like the shims npm creates on Windows.
+    #[cfg(unix)]
+    pub fn node_mirror(&self) -> String {
+        use std::os::unix::fs::PermissionsExt;
+
+        use sha2::{Digest, Sha256};
+
+        let os = match std::env::consts::OS {
+            "macos" => "darwin",
+            os => os,
+        };
+        let arch = match std::env::consts::ARCH {
+            "x86_64" => "x64",
+            "aarch64" => "arm64",
+            arch => arch,
+        };
+        let name = format!("node-v99.0.0-{os}-{arch}");
+        let filename = format!("{name}.tar.xz");
+
+        let release = self.temp_dir.child("release");
+        let bin = release.child(&name).child("bin");
+        let scripts = [
+            ("node", "#!/bin/sh\necho v99.0.0\n"),
+            (
+                "npm",
+                indoc::indoc! {r#"
+                    #!/bin/sh
+                    for pkg in "$@"; do
+                      case "$pkg" in -*|install) continue ;; esac
+                      mkdir -p "$NPM_CONFIG_PREFIX/lib/node_modules/$pkg"
+                      printf '#!/bin/sh\necho "%s says hello"\n' "$pkg" > "$NPM_CONFIG_PREFIX/lib/node_modules/$pkg/cli"
+                      printf '#!/bin/sh\nexec "%s/lib/node_modules/%s/cli"\n' "$NPM_CONFIG_PREFIX" "$pkg" > "$NPM_CONFIG_PREFIX/bin/$pkg"
+                      chmod +x "$NPM_CONFIG_PREFIX/lib/node_modules/$pkg/cli" "$NPM_CONFIG_PREFIX/bin/$pkg"
+                    done
+                "#},
+            ),
+        ];
+        for (file, content) in scripts {
+            let script = bin.child(file);
+            script.write_str(content).expect("Failed to write script");
+            fs_err::set_permissions(script.path(), std::fs::Permissions::from_mode(0o755))
+                .expect("Failed to set permissions");
+        }
+        Command::new("tar")
+            .arg("-cJf")
+            .arg(&filename)
+            .arg(&name)
+            .current_dir(&release)
+            .assert()
+            .success();
+        let archive =
+            fs_err::read(release.child(&filename).path()).expect("Failed to read archive");
+        fs_err::remove_dir_all(release.path()).expect("Failed to remove release");
+
+        let checksums = format!("{:x}  {filename}\n", Sha256::digest(&archive));
+        serve_files(vec![
+            (
+                "/index.json".to_string(),
+                br#"[{"version": "v99.0.0", "lts": false}]"#.to_vec(),
+            ),
+            (
+                "/v99.0.0/SHASUMS256.txt".to_string(),
+                checksums.into_bytes(),
+            ),
+            (format!("/v99.0.0/{filename}"), archive),
+        ])
+    }
 }
 
+/// A local `greeter` hook, as a flow mapping to fit in any config, running the `greeter`
+/// package installed by the fake Node.js release of [`TestContext::node_mirror`].
+pub const GREETER_HOOK: &str = "{id: greeter, name: greeter, language: node, language_version: '99.0.0', entry: greeter, additional_dependencies: [greeter], always_run: true, pass_filenames: false, verbose: true}";
+
 #[doc(hidden)] // Macro and test context only, don't use directly.
 pub const INSTA_FILTERS: &[(&str, &str)] = &[
     // File sizes
@@ -336,3 +496,50 @@ pub(crate) fn remove_bin_from_path(bin: &str) -> anyhow::Result<OsString> {
 
     Ok(std::env::join_paths(new_path_entries)?)
 }
+
+/// Serve files over HTTP on a local port, like a download mirror, returns its url.
+///
+/// `files` maps a request path, with its query, to the response body. The server runs until
+/// the test exits.
+pub(crate) fn serve_files(files: Vec<(String, Vec<u8>)>) -> String {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind server");
+    let url = format!(
+        "http://{}",
+        listener.local_addr().expect("Failed to get address")
+    );
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(&stream);
+            let mut request = String::new();
+            if reader.read_line(&mut request).is_err() {
+                continue;
+            }
+            // Skip the headers.
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                line.clear();
+            }
+
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let response = match files.iter().find(|(file, _)| file == path) {
+                Some((_, body)) => {
+                    let mut response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    )
+                    .into_bytes();
+                    response.extend_from_slice(body);
+                    response
+                }
+                None => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_vec(),
+            };
+            (&stream).write_all(&response).ok();
+        }
+    });
+
+    url
+}
@@ -1,5 +1,6 @@
 use std::process::Command;
 
+use assert_fs::fixture::{FileWriteStr, PathChild};
 use common::TestContext;
 use indoc::indoc;
 
@@ -56,3 +57,68 @@ fn hook_impl() {
       .pre-commit-config.yaml
     "#);
 }
+
+/// The installed hook runs the hooks of nested projects too.
+#[test]
+fn workspace_hook_impl() -> anyhow::Result<()> {
+    let context = TestContext::new();
+
+    context.init_project();
+
+    let config = indoc! { r"
+        repos:
+        - repo: local
+          hooks:
+           - id: cat
+             name: cat
+             language: system
+             entry: cat
+             files: \.txt$
+             verbose: true
+    "};
+    let cwd = context.work_dir();
+    context.write_pre_commit_config(config);
+    cwd.child("a.txt").write_str("root\n")?;
+    cwd.child("project-1/.pre-commit-config.yaml")
+        .write_str(config)?;
+    cwd.child("project-1/a.txt").write_str("project-1\n")?;
+
+    context.git_add(".");
+    context.configure_git_author();
+    let mut commit = Command::new("git");
+    commit
+        .arg("commit")
+        .arg("--quiet")
+        .current_dir(context.work_dir())
+        .arg("-m")
+        .arg("Initial commit");
+
+    cmd_snapshot!(context.filters(), context.install(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    prek installed at .git/hooks/pre-commit
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), commit, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Running hooks for `.`:
+    cat......................................................................Passed
+    - hook id: cat
+    - duration: [TIME]
+      root
+    Running hooks for `project-1`:
+    cat......................................................................Passed
+    - hook id: cat
+    - duration: [TIME]
+      project-1
+    ");
+
+    Ok(())
+}
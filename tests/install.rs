@@ -154,6 +154,30 @@ fn install() -> anyhow::Result<()> {
 }
 
 /// Run `prek install --install-hooks` to install the git hook and create prek hook environments.
+/// `default_install_hook_types` is inherited from the base configs in `extends`.
+#[test]
+fn install_extends() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context
+        .work_dir()
+        .child("base.yaml")
+        .write_str("default_install_hook_types: [pre-push]\nrepos: []\n")?;
+    context.write_pre_commit_config("extends: [base.yaml]\nrepos: []\n");
+
+    cmd_snapshot!(context.filters(), context.install(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    prek installed at .git/hooks/pre-push
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn install_with_hooks() -> anyhow::Result<()> {
     let context = TestContext::new();
@@ -1,5 +1,6 @@
 use assert_fs::assert::PathAssert;
 use assert_fs::fixture::PathChild;
+use constants::env_vars::EnvVars;
 
 use crate::common::{TestContext, cmd_snapshot};
 
@@ -74,30 +75,30 @@ fn language_version() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+    [golang] go version go1.24.5 [OS]/[ARCH]
     golang...................................................................Passed
     - hook id: golang
     - duration: [TIME]
-      go version go1.24.5 [OS]/[ARCH]
+    [golang] go version go1.24.5 [OS]/[ARCH]
     golang...................................................................Passed
     - hook id: golang
     - duration: [TIME]
-      go version go1.24.5 [OS]/[ARCH]
+    [golang] go version go1.23.11 [OS]/[ARCH]
     golang...................................................................Passed
     - hook id: golang
     - duration: [TIME]
-      go version go1.23.11 [OS]/[ARCH]
+    [golang] go version go1.23.11 [OS]/[ARCH]
     golang...................................................................Passed
     - hook id: golang
     - duration: [TIME]
-      go version go1.23.11 [OS]/[ARCH]
+    [golang] go version go1.23.11 [OS]/[ARCH]
     golang...................................................................Passed
     - hook id: golang
     - duration: [TIME]
-      go version go1.23.11 [OS]/[ARCH]
+    [golang] go version go1.24.5 [OS]/[ARCH]
     golang...................................................................Passed
     - hook id: golang
     - duration: [TIME]
-      go version go1.24.5 [OS]/[ARCH]
 
     ----- stderr -----
     "#);
@@ -192,3 +193,81 @@ fn remote_hook() {
     ----- stderr -----
     "#);
 }
+
+/// Go is downloaded from the mirror, with its versions and checksums read from the release
+/// feed of the mirror.
+#[cfg(unix)]
+#[test]
+fn mirror() -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    use assert_fs::fixture::FileWriteStr;
+    use sha2::{Digest, Sha256};
+
+    use crate::common::serve_files;
+
+    let context = TestContext::new();
+    context.init_project();
+
+    // A fake Go release.
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        os => os,
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        arch => arch,
+    };
+    let filename = format!("go1.99.0.{os}-{arch}.tar.gz");
+    let release = context.work_dir().child("release");
+    let go = release.child("go").child("bin").child("go");
+    go.write_str("#!/bin/sh\necho 'go version go1.99.0 mirrored'\n")?;
+    fs_err::set_permissions(go.path(), std::fs::Permissions::from_mode(0o755))?;
+    std::process::Command::new("tar")
+        .arg("-czf")
+        .arg(&filename)
+        .arg("go")
+        .current_dir(&release)
+        .status()?;
+    let archive = fs_err::read(release.child(&filename).path())?;
+    fs_err::remove_dir_all(release.path())?;
+
+    let feed = format!(
+        r#"[{{"version": "go1.99.0", "files": [{{"filename": "{filename}", "sha256": "{:x}"}}]}}]"#,
+        Sha256::digest(&archive)
+    );
+    let mirror = serve_files(vec![
+        ("/?mode=json&include=all".to_string(), feed.into_bytes()),
+        (format!("/{filename}"), archive),
+    ]);
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: golang
+                name: golang
+                language: golang
+                entry: go version
+                language_version: '1.99'
+                always_run: true
+                pass_filenames: false
+                verbose: true
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().env(EnvVars::PREK_GO_MIRROR, &mirror), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    golang...................................................................Passed
+    - hook id: golang
+    - duration: [TIME]
+      go version go1.99.0 mirrored
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use assert_cmd::assert::OutputAssertExt;
 use assert_fs::assert::PathAssert;
 use assert_fs::fixture::{FileWriteStr, PathChild};
 
@@ -61,30 +62,30 @@ fn language_version() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+    [node] v19.9.0
     node.....................................................................Passed
     - hook id: node
     - duration: [TIME]
-      v19.9.0
+    [node] v19.9.0
     node.....................................................................Passed
     - hook id: node
     - duration: [TIME]
-      v19.9.0
+    [node] v18.20.8
     node.....................................................................Passed
     - hook id: node
     - duration: [TIME]
-      v18.20.8
+    [node] v18.20.8
     node.....................................................................Passed
     - hook id: node
     - duration: [TIME]
-      v18.20.8
+    [node] v19.9.0
     node.....................................................................Passed
     - hook id: node
     - duration: [TIME]
-      v19.9.0
+    [node] v18.20.8
     node.....................................................................Passed
     - hook id: node
     - duration: [TIME]
-      v18.20.8
 
     ----- stderr -----
     "#);
@@ -202,3 +203,126 @@ fn doctoc() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// A broken environment is reported by `prek doctor` and rebuilt by `prek run`.
+#[test]
+fn broken_env() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_node_hook_config();
+    context.git_add(".");
+
+    // Before the default filters, the random part of the name may look like a duration.
+    let filters: Vec<_> = [(r"node-[A-Za-z0-9]+", "node-[HASH]")]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
+
+    context.run().assert().success();
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    node: healthy `[HOME]/hooks/node-[HASH]`
+
+    ----- stderr -----
+    ");
+
+    // The toolchain the environment was built from is gone.
+    let env = fs_err::read_dir(context.home_dir().child("hooks").path())?
+        .next()
+        .expect("Node environment should be installed")?
+        .path();
+    fs_err::remove_file(env.join("bin").join("node"))?;
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    node: broken `[HOME]/hooks/node-[HASH]`
+      `[HOME]/hooks/node-[HASH]/bin/node` does not exist
+
+    Found 1 broken environment(s), run `prek install-hooks` to rebuild them
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(filters.clone(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    node.....................................................................Passed
+
+    ----- stderr -----
+    warning: The environment of hook `node` at `[HOME]/hooks/node-[HASH]` is broken, reinstalling: `[HOME]/hooks/node-[HASH]/bin/node` does not exist
+    ");
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    node: healthy `[HOME]/hooks/node-[HASH]`
+
+    ----- stderr -----
+    ");
+
+    // The broken environment is removed once no process uses the store.
+    assert!(!env.exists());
+
+    Ok(())
+}
+
+/// An entry not found in the environment is only a warning, the environment isn't rebuilt.
+#[test]
+fn entry_not_found() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: node
+                name: node
+                language: node
+                entry: ./not-yet-built.js
+    "});
+    context.git_add(".");
+
+    let filters: Vec<_> = [(r"node-[A-Za-z0-9]+", "node-[HASH]")]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
+
+    context.install_hooks().assert().success();
+
+    cmd_snapshot!(filters.clone(), context.install_hooks(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    // The environment was reused.
+    let envs = fs_err::read_dir(context.home_dir().child("hooks").path())?
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .is_ok_and(|entry| entry.file_name().to_string_lossy().starts_with("node-"))
+        })
+        .count();
+    assert_eq!(envs, 1);
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    node: warning `[HOME]/hooks/node-[HASH]`
+      Entry `./not-yet-built.js` not found
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
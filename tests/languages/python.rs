@@ -1,3 +1,4 @@
+use assert_cmd::assert::OutputAssertExt;
 use assert_fs::assert::PathAssert;
 use assert_fs::fixture::PathChild;
 
@@ -74,38 +75,38 @@ fn language_version() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+    [python3] Hello, World!
     python3..................................................................Passed
     - hook id: python3
     - duration: [TIME]
-      Hello, World!
+    [python3.12] (3, 12, 11)
     python3.12...............................................................Passed
     - hook id: python3.12
     - duration: [TIME]
-      (3, 12, 11)
+    [python3.12] (3, 12, 11)
     python3.12...............................................................Passed
     - hook id: python3.12
     - duration: [TIME]
-      (3, 12, 11)
+    [python3.12] (3, 12, 11)
     python3.12...............................................................Passed
     - hook id: python3.12
     - duration: [TIME]
-      (3, 12, 11)
+    [python3.12] (3, 12, 11)
     python3.12...............................................................Passed
     - hook id: python3.12
     - duration: [TIME]
-      (3, 12, 11)
+    [python3.12] (3, 12, 11)
     python3.12...............................................................Passed
     - hook id: python3.12
     - duration: [TIME]
-      (3, 12, 11)
+    [greater-than-python3.13] (3, 13, 5)
     greater-than-python3.13..................................................Passed
     - hook id: greater-than-python3.13
     - duration: [TIME]
-      (3, 13, 5)
+    [python3.12] (3, 12, 1)
     python3.12...............................................................Passed
     - hook id: python3.12
     - duration: [TIME]
-      (3, 12, 1)
 
     ----- stderr -----
     "#);
@@ -154,6 +155,14 @@ fn invalid_version() {
     ----- stderr -----
     error: Hook `local` is invalid
       caused by: Invalid `language_version` value: `invalid-version`
+
+       ╭─[.pre-commit-config.yaml:8:9]
+     7 │         entry: python -c 'print("Hello, world!")'
+     8 │         language_version: 'invalid-version' # invalid version
+       ·         ────────┬───────
+       ·                 ╰── invalid version
+     9 │         always_run: true
+       ╰────
     "#);
 }
 
@@ -241,3 +250,76 @@ fn additional_dependencies() {
     ----- stderr -----
     "#);
 }
+
+/// A venv whose base interpreter is gone is reported by `prek doctor` and rebuilt by `prek run`.
+#[test]
+fn broken_env() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: python
+                name: python
+                language: python
+                entry: python -c 'print("hello")'
+                language_version: '3.12.1' # will auto download
+                always_run: true
+                pass_filenames: false
+    "#});
+    context.git_add(".");
+
+    // Before the default filters, the random part of the name may look like a duration.
+    let filters: Vec<_> = [(r"python-[A-Za-z0-9]+", "python-[HASH]")]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
+
+    context.run().assert().success();
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    python: healthy `[HOME]/hooks/python-[HASH]`
+
+    ----- stderr -----
+    ");
+
+    // Remove the downloaded Python the venv was created from.
+    fs_err::remove_dir_all(context.home_dir().child("tools").child("python").path())?;
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    python: broken `[HOME]/hooks/python-[HASH]`
+      `[HOME]/hooks/python-[HASH]/bin/python` does not exist
+
+    Found 1 broken environment(s), run `prek install-hooks` to rebuild them
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(filters.clone(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    python...................................................................Passed
+
+    ----- stderr -----
+    warning: The environment of hook `python` at `[HOME]/hooks/python-[HASH]` is broken, reinstalling: `[HOME]/hooks/python-[HASH]/bin/python` does not exist
+    ");
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    python: healthy `[HOME]/hooks/python-[HASH]`
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
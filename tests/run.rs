@@ -87,7 +87,7 @@ fn invalid_config() {
     context.write_pre_commit_config("invalid: config");
     context.git_add(".");
 
-    cmd_snapshot!(context.filters(), context.run(), @r#"
+    cmd_snapshot!(context.filters(), context.run(), @r"
     success: false
     exit_code: 2
     ----- stdout -----
@@ -95,7 +95,13 @@ fn invalid_config() {
     ----- stderr -----
     error: Failed to parse `.pre-commit-config.yaml`
       caused by: missing field `repos`
-    "#);
+
+       ╭─[.pre-commit-config.yaml:1:1]
+     1 │ invalid: config
+       · ────┬───
+       ·     ╰── missing field `repos`
+       ╰────
+    ");
 
     context.write_pre_commit_config(indoc::indoc! {r#"
         repos:
@@ -117,6 +123,14 @@ fn invalid_config() {
     ----- stderr -----
     error: Hook `trailing-whitespace` is invalid
       caused by: Hook specified `additional_dependencies` `dotnet@6` but the language `dotnet` does not support installing dependencies for now
+
+       ╭─[.pre-commit-config.yaml:7:9]
+     6 │         language: dotnet
+     7 │         additional_dependencies: ["dotnet@6"]
+       ·         ───────────┬───────────
+       ·                    ╰── `dotnet` does not support installing dependencies
+     8 │         entry: echo Hello, world!
+       ╰────
     "#);
 
     context.write_pre_commit_config(indoc::indoc! {r"
@@ -131,7 +145,7 @@ fn invalid_config() {
     "});
     context.git_add(".");
 
-    cmd_snapshot!(context.filters(), context.run(), @r#"
+    cmd_snapshot!(context.filters(), context.run(), @r"
     success: false
     exit_code: 2
     ----- stdout -----
@@ -139,7 +153,45 @@ fn invalid_config() {
     ----- stderr -----
     error: Hook `trailing-whitespace` is invalid
       caused by: Hook specified `language_version` `6` but the language `fail` does not install an environment
-    "#);
+
+       ╭─[.pre-commit-config.yaml:7:9]
+     6 │         language: fail
+     7 │         language_version: '6'
+       ·         ────────┬───────
+       ·                 ╰── `fail` does not install an environment
+     8 │         entry: echo Hello, world!
+       ╰────
+    ");
+
+    // Invalid regex in `files`.
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: trailing-whitespace
+                name: trailing-whitespace
+                language: system
+                entry: echo Hello, world!
+                files: '(foo'
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Invalid file pattern in `.pre-commit-config.yaml`
+      caused by: `files` of hook `trailing-whitespace` is not a valid regex: Parsing error at position 4: Opening parenthesis without closing parenthesis
+
+       ╭─[.pre-commit-config.yaml:8:9]
+     7 │         entry: echo Hello, world!
+     8 │         files: '(foo'
+       ·         ──┬──
+       ·           ╰── Parsing error at position 4: Opening parenthesis without closing parenthesis
+       ╰────
+    ");
 }
 
 /// Use same repo multiple times, with same or different revisions.
@@ -563,6 +615,59 @@ fn files_and_exclude() -> Result<()> {
     Ok(())
 }
 
+/// Test `files` and `exclude` given as globs.
+#[test]
+fn glob_patterns() -> Result<()> {
+    let context = TestContext::new();
+
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("src/main.rs").write_str("fn main() {}\n")?;
+    cwd.child("src/nested/lib.rs").write_str("\n")?;
+    cwd.child("src/generated/schema.rs").write_str("\n")?;
+    cwd.child("build.rs").write_str("fn main() {}\n")?;
+    cwd.child("README.md").write_str("# README\n")?;
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        exclude: {glob: 'src/generated/**'}
+        repos:
+          - repo: local
+            hooks:
+              - id: rust-files
+                name: rust files
+                language: system
+                entry: python3 -c 'import sys; print(sorted(sys.argv[1:]))'
+                files: {glob: ['src/**/*.rs', '*.md']}
+                verbose: true
+              - id: top-level
+                name: top level
+                language: system
+                entry: python3 -c 'import sys; print(sorted(sys.argv[1:]))'
+                files: {glob: '*.rs'}
+                verbose: true
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    rust files...............................................................Passed
+    - hook id: rust-files
+    - duration: [TIME]
+      ['README.md', 'src/main.rs', 'src/nested/lib.rs']
+    top level................................................................Passed
+    - hook id: top-level
+    - duration: [TIME]
+      ['build.rs']
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Test selecting files by type, `types`, `types_or`, and `exclude_types`.
 #[test]
 fn file_types() -> Result<()> {
@@ -1031,10 +1136,10 @@ fn alternate_config_file() {
     success: true
     exit_code: 0
     ----- stdout -----
+    [local-python-hook] Hello, world!
     local-python-hook........................................................Passed
     - hook id: local-python-hook
     - duration: [TIME]
-      Hello, world!
 
     ----- stderr -----
     "#);
@@ -1364,3 +1469,778 @@ fn run_directory() -> Result<()> {
 
     Ok(())
 }
+
+/// Test hook `stream` option.
+#[test]
+fn stream_output() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: stream
+                name: stream
+                language: system
+                entry: python3 -c 'print("line 1"); print("line 2"); exit(1)'
+                always_run: true
+                pass_filenames: false
+                stream: true
+              - id: no-stream
+                name: no-stream
+                language: system
+                entry: python3 -c 'print("line 1"); exit(1)'
+                always_run: true
+                pass_filenames: false
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    [stream] line 1
+    [stream] line 2
+    stream...................................................................Failed
+    - hook id: stream
+    - exit code: 1
+    no-stream................................................................Failed
+    - hook id: no-stream
+    - exit code: 1
+      line 1
+
+    ----- stderr -----
+    "#);
+}
+
+/// Test `prek run --dry-run`.
+#[test]
+fn dry_run() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                language: system
+                entry: echo 'hello world'
+                args: [--flag]
+                files: \.txt$
+                require_serial: true
+              - id: no-filenames
+                name: no-filenames
+                language: system
+                entry: touch should-not-exist
+                always_run: true
+                pass_filenames: false
+              - id: skipped
+                name: skipped
+                language: system
+                entry: echo
+              - id: pre-push
+                name: pre-push
+                language: system
+                entry: echo
+                stages: [pre-push]
+              - id: unsupported
+                name: unsupported
+                language: rust
+                entry: echo
+              - id: no-files
+                name: no-files
+                language: system
+                entry: echo
+                files: \.py$
+              - id: script
+                name: script
+                language: script
+                entry: scripts/check.sh --strict
+                files: a\.txt$
+              - id: node
+                name: node
+                language: node
+                entry: eslint
+                files: a\.txt$
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("a.txt").write_str("a")?;
+    cwd.child("b.txt").write_str("b")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--dry-run").env("SKIP", "skipped").env("PREK_NO_CONCURRENCY", "1"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo
+    - hook id: echo
+    - files: 2
+        a.txt
+        b.txt
+    - commands: 1 (run serially)
+        echo 'hello world' --flag b.txt a.txt
+    no-filenames
+    - hook id: no-filenames
+    - files: 3
+        .pre-commit-config.yaml
+        a.txt
+        b.txt
+    - commands: 1 (run serially)
+        touch should-not-exist
+    skipped
+    - hook id: skipped
+    - skipped: hook is skipped by `--skip` or `SKIP`
+    pre-push
+    - hook id: pre-push
+    - skipped: hook does not run at stage `pre-commit`
+    unsupported
+    - hook id: unsupported
+    - skipped: language `rust` is not implemented yet
+    no-files
+    - hook id: no-files
+    - skipped: no files to check
+    script
+    - hook id: script
+    - files: 1
+        a.txt
+    - commands: 1 (run serially)
+        scripts/check.sh --strict a.txt
+    node
+    - hook id: node
+    - files: 1
+        a.txt
+    - commands: 1 (run serially)
+        eslint a.txt
+    - runs in: the hook environment, with its executables first in `PATH`
+
+    ----- stderr -----
+    ");
+
+    cwd.child("should-not-exist")
+        .assert(predicates::path::missing());
+
+    // Nothing is stashed, so the config doesn't need to be staged.
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                language: system
+                entry: echo
+                files: a\.txt$
+    "});
+    cmd_snapshot!(context.filters(), context.run().arg("--dry-run"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo
+    - hook id: echo
+    - files: 1
+        a.txt
+    - commands: 1 (run serially)
+        echo a.txt
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// Select hooks by id, `--skip`, `--repo` and `--language`.
+#[test]
+fn select_hooks() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: hook-1
+                name: hook-1
+                language: system
+                entry: echo
+                always_run: true
+              - id: hook-2
+                name: hook-2
+                language: system
+                entry: echo
+                always_run: true
+              - id: hook-3
+                name: hook-3
+                language: fail
+                entry: fail
+                always_run: true
+          - repo: meta
+            hooks:
+              - id: identity
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("hook-1").arg("hook-3"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    hook-1...................................................................Passed
+    hook-3...................................................................Failed
+    - hook id: hook-3
+    - exit code: 1
+      fail
+
+      .pre-commit-config.yaml
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--skip").arg("hook-1").env("SKIP", "hook-3"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hook-1..................................................................Skipped
+    hook-2...................................................................Passed
+    hook-3..................................................................Skipped
+    identity.................................................................Passed
+    - hook id: identity
+    - duration: [TIME]
+      .pre-commit-config.yaml
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--language").arg("fail"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    hook-3...................................................................Failed
+    - hook id: hook-3
+    - exit code: 1
+      fail
+
+      .pre-commit-config.yaml
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--repo").arg("meta"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    identity.................................................................Passed
+    - hook id: identity
+    - duration: [TIME]
+      .pre-commit-config.yaml
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("hook-1").arg("hook-4"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    No hook found for id `hook-4` and stage `pre-commit`
+    ");
+}
+
+/// Select hooks by `--tag` and `--group`.
+#[test]
+fn tags_and_groups() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        groups:
+          fast: [hook-1, hook-2]
+          slow: [hook-3]
+          typo: [hook-1, hook-4]
+          mixed: [hook-2, hook-5]
+        repos:
+          - repo: local
+            hooks:
+              - id: hook-1
+                name: hook-1
+                language: system
+                entry: echo
+                always_run: true
+                tags: [lint]
+              - id: hook-2
+                name: hook-2
+                language: system
+                entry: echo
+                always_run: true
+              - id: hook-3
+                name: hook-3
+                language: system
+                entry: echo
+                always_run: true
+                tags: [lint, slow]
+              - id: hook-5
+                name: hook-5
+                language: system
+                entry: echo
+                always_run: true
+                stages: [manual]
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--tag").arg("lint"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hook-1...................................................................Passed
+    hook-3...................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--group").arg("fast"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hook-1...................................................................Passed
+    hook-2...................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--group").arg("slow").arg("--tag").arg("lint").arg("--skip").arg("hook-1"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hook-1..................................................................Skipped
+    hook-3...................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--group").arg("unknown"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    No group named `unknown` found in the config
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--group").arg("typo"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    No hook found for id `hook-4` of group `typo`
+    ");
+
+    // Hooks of the group in other stages are just not run.
+    cmd_snapshot!(context.filters(), context.run().arg("--group").arg("mixed"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hook-2...................................................................Passed
+
+    ----- stderr -----
+    ");
+}
+
+/// Run hooks in a workspace with nested projects.
+#[test]
+fn workspace() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let config = indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: cat
+                name: cat
+                language: system
+                entry: cat
+                files: \.txt$
+                verbose: true
+    "};
+    let cwd = context.work_dir();
+    context.write_pre_commit_config(config);
+    cwd.child("a.txt").write_str("root\n")?;
+    cwd.child("project-1/.pre-commit-config.yaml")
+        .write_str(config)?;
+    cwd.child("project-1/a.txt").write_str("project-1\n")?;
+    cwd.child("project-1/project-2/.pre-commit-config.yaml")
+        .write_str(config)?;
+    cwd.child("project-1/project-2/a.txt")
+        .write_str("project-2\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Running hooks for `.`:
+    cat......................................................................Passed
+    - hook id: cat
+    - duration: [TIME]
+      root
+    Running hooks for `project-1`:
+    cat......................................................................Passed
+    - hook id: cat
+    - duration: [TIME]
+      project-1
+    Running hooks for `project-1/project-2`:
+    cat......................................................................Passed
+    - hook id: cat
+    - duration: [TIME]
+      project-2
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--cd").arg("project-1/project-2"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    cat......................................................................Passed
+    - hook id: cat
+    - duration: [TIME]
+      project-2
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--cd").arg("project-3"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No project found in directory `project-3`
+    ");
+
+    // The config in the root, like the one passed by the git hook scripts, is the root of the
+    // workspace.
+    cmd_snapshot!(context.filters(), context.run().arg("--config").arg(".pre-commit-config.yaml").arg("--cd").arg("project-1"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    cat......................................................................Passed
+    - hook id: cat
+    - duration: [TIME]
+      project-1
+
+    ----- stderr -----
+    ");
+
+    // Other configs are projects of their own.
+    cmd_snapshot!(context.filters(), context.run().arg("--config").arg("project-1/.pre-commit-config.yaml").arg("--cd").arg("project-1"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `--cd` can't be used with `--config` `project-1/.pre-commit-config.yaml`, which is not in the root of the repo
+    ");
+
+    Ok(())
+}
+
+/// Inherit hooks from base configs with `extends`.
+#[test]
+fn extends() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("configs/base.yaml").write_str(indoc::indoc! {r"
+        extends: [common.yaml]
+        repos:
+          - repo: local
+            hooks:
+              - id: base-1
+                name: base-1
+                language: system
+                entry: echo base-1
+                always_run: true
+                pass_filenames: false
+                verbose: true
+              - id: base-2
+                name: base-2
+                language: system
+                entry: echo base-2
+                always_run: true
+                pass_filenames: false
+                verbose: true
+    "})?;
+    cwd.child("configs/common.yaml")
+        .write_str(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: common
+                name: common
+                language: system
+                entry: echo common
+                always_run: true
+                pass_filenames: false
+                verbose: true
+    "})?;
+    context.write_pre_commit_config(indoc::indoc! {r"
+        extends: [configs/base.yaml]
+        disabled_hooks: [common]
+        repos:
+          - repo: local
+            hooks:
+              - id: base-2
+                name: base-2
+                language: system
+                entry: echo base-2 overridden
+                always_run: true
+                pass_filenames: false
+                verbose: true
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    base-1...................................................................Passed
+    - hook id: base-1
+    - duration: [TIME]
+      base-1
+    base-2...................................................................Passed
+    - hook id: base-2
+    - duration: [TIME]
+      base-2 overridden
+
+    ----- stderr -----
+    ");
+
+    // Detect cycles.
+    cwd.child("configs/common.yaml")
+        .write_str("extends: [base.yaml]\nrepos: []\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Configs extend each other in a cycle: `configs/base.yaml` -> `configs/common.yaml` -> `configs/base.yaml`
+    ");
+
+    Ok(())
+}
+
+/// Global settings and hooks from `config.toml` in `PREK_CONFIG_HOME`.
+#[test]
+fn global_config() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context
+        .config_dir()
+        .child("config.toml")
+        .write_str(indoc::indoc! {r#"
+        color = "never"
+
+        [[repos]]
+        repo = "local"
+        hooks = [
+            { id = "global", name = "global", language = "system", entry = "echo global", always_run = true, pass_filenames = false, verbose = true },
+            { id = "local", name = "local", language = "system", entry = "echo overridden", always_run = true, pass_filenames = false, verbose = true },
+        ]
+    "#})?;
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: local
+                name: local
+                language: system
+                entry: echo local
+                always_run: true
+                pass_filenames: false
+                verbose: true
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    global...................................................................Passed
+    - hook id: global
+    - duration: [TIME]
+      global
+    local....................................................................Passed
+    - hook id: local
+    - duration: [TIME]
+      local
+
+    ----- stderr -----
+    ");
+
+    context
+        .config_dir()
+        .child("config.toml")
+        .write_str("concurrency = -1\n")?;
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to parse global config `[CONFIG_HOME]/config.toml`
+      caused by: TOML parse error at line 1, column 15
+      |
+    1 | concurrency = -1
+      |               ^^
+    invalid value: integer `-1`, expected usize
+    ");
+
+    Ok(())
+}
+
+/// Run hooks from `prek.toml`, which takes precedence over `.pre-commit-config.yaml`.
+#[test]
+fn prek_toml() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context
+        .work_dir()
+        .child("prek.toml")
+        .write_str(indoc::indoc! {r#"
+        [[repos]]
+        repo = "local"
+
+        [[repos.hooks]]
+        id = "toml"
+        name = "toml"
+        language = "system"
+        entry = "echo toml"
+        always_run = true
+        pass_filenames = false
+        verbose = true
+    "#})?;
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: yaml
+                name: yaml
+                language: system
+                entry: echo yaml
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    toml.....................................................................Passed
+    - hook id: toml
+    - duration: [TIME]
+      toml
+
+    ----- stderr -----
+    warning: Both [TEMP_DIR]/prek.toml and [TEMP_DIR]/.pre-commit-config.yaml exist, using [TEMP_DIR]/prek.toml
+    ");
+
+    Ok(())
+}
+
+/// In offline mode, everything that is missing is reported at once.
+#[test]
+fn offline() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+            rev: v5.0.0
+            hooks:
+              - id: trailing-whitespace
+          - repo: https://github.com/pre-commit/mirrors-prettier
+            rev: v3.1.0
+            hooks:
+              - id: prettier
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--offline"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Offline mode is enabled, but the following are not available locally:
+      - repo `https://github.com/pre-commit/mirrors-prettier@v3.1.0`
+      - repo `https://github.com/pre-commit/pre-commit-hooks@v5.0.0`
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: node
+                name: node
+                language: node
+                language_version: '99.0.0'
+                entry: node
+              - id: node-deps
+                name: node-deps
+                language: node
+                language_version: '99.0.0'
+                entry: node
+                additional_dependencies: [left-pad]
+              - id: go
+                name: go
+                language: golang
+                language_version: '1.99'
+                entry: go
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().env("PREK_OFFLINE", "1"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Offline mode is enabled, but the following are not available locally:
+      - Go 1.99
+      - Node.js 99.0.0
+    ");
+
+    // Dependencies that are not in the npm cache are reported as missing.
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: node-deps
+                name: node-deps
+                language: node
+                entry: node
+                additional_dependencies: ['@prek-test/not-cached']
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--offline"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Offline mode is enabled, but the following are not available locally:
+      - npm package `@prek-test/not-cached`
+    ");
+}
@@ -68,3 +68,147 @@ fn sample_config() {
           - id: check-added-large-files
     "##);
 }
+
+#[test]
+fn sample_config_toml() {
+    let context = TestContext::new();
+
+    cmd_snapshot!(context.filters(), context.sample_config().arg("--format").arg("toml"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # See https://pre-commit.com for more information
+    # See https://pre-commit.com/hooks.html for more hooks
+    [[repos]]
+    repo = "https://github.com/pre-commit/pre-commit-hooks"
+    rev = "v5.0.0"
+    hooks = [
+      { id = "trailing-whitespace" },
+      { id = "end-of-file-fixer" },
+      { id = "check-yaml" },
+      { id = "check-added-large-files" },
+    ]
+
+    ----- stderr -----
+    "#);
+
+    cmd_snapshot!(context.filters(), context.sample_config().arg("-f").arg("--format").arg("toml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Written to `prek.toml`
+
+    ----- stderr -----
+    ");
+
+    // The format is detected from the file extension.
+    cmd_snapshot!(context.filters(), context.sample_config().arg("-f").arg("sample.toml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Written to `sample.toml`
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(context.read("prek.toml"), context.read("sample.toml"));
+}
+
+#[test]
+fn convert_config() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        # Comments are not preserved.
+        fail_fast: true
+        exclude:
+        repos:
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+            rev: v5.0.0
+            hooks:
+              - id: trailing-whitespace
+                args: [--markdown-linebreak-ext=md]
+          - repo: local
+            hooks:
+              - id: cargo-fmt
+                name: cargo fmt
+                entry: cargo fmt --
+                language: system
+                types: [rust]
+    "});
+
+    cmd_snapshot!(context.filters(), context.command().arg("convert-config").arg(".pre-commit-config.yaml").arg("-o").arg("prek.toml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Written to `prek.toml`
+
+    ----- stderr -----
+    ");
+
+    insta::assert_snapshot!(context.read("prek.toml"), @r#"
+    fail_fast = true
+
+    [[repos]]
+    repo = "https://github.com/pre-commit/pre-commit-hooks"
+    rev = "v5.0.0"
+
+    [[repos.hooks]]
+    id = "trailing-whitespace"
+    args = ["--markdown-linebreak-ext=md"]
+
+    [[repos]]
+    repo = "local"
+
+    [[repos.hooks]]
+    id = "cargo-fmt"
+    name = "cargo fmt"
+    entry = "cargo fmt --"
+    language = "system"
+    types = ["rust"]
+    "#);
+
+    cmd_snapshot!(context.filters(), context.command().arg("convert-config").arg("prek.toml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    fail_fast: true
+    repos:
+    - repo: https://github.com/pre-commit/pre-commit-hooks
+      rev: v5.0.0
+      hooks:
+      - id: trailing-whitespace
+        args:
+        - --markdown-linebreak-ext=md
+    - repo: local
+      hooks:
+      - id: cargo-fmt
+        name: cargo fmt
+        entry: cargo fmt --
+        language: system
+        types:
+        - rust
+
+    ----- stderr -----
+    ");
+
+    // Invalid configs are not converted.
+    context.write_pre_commit_config("repos: {}\n");
+
+    cmd_snapshot!(context.filters(), context.command().arg("convert-config").arg(".pre-commit-config.yaml"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to parse `.pre-commit-config.yaml`
+      caused by: repos: invalid type: map, expected a sequence at line 1 column 8
+
+       ╭─[.pre-commit-config.yaml:1:8]
+     1 │ repos: {}
+       ·        ─┬
+       ·         ╰── repos: invalid type: map, expected a sequence
+       ╰────
+    ");
+}
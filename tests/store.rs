@@ -0,0 +1,311 @@
+use std::process::Command;
+use std::time::Duration;
+
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use constants::env_vars::EnvVars;
+
+#[cfg(unix)]
+use crate::common::GREETER_HOOK;
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+const HOOKS: &str = indoc::indoc! {r"
+    - id: hello
+      name: hello
+      language: system
+      entry: echo hello
+      always_run: true
+"};
+
+/// Revs resolving to the same commit share a clone in the store.
+#[test]
+fn clone_keyed_by_commit() -> anyhow::Result<()> {
+    let hook_repo = TestContext::new();
+    let repo_url = hook_repo.init_hook_repo(HOOKS);
+    let output = Command::new("git")
+        .arg("rev-parse")
+        .arg("HEAD")
+        .current_dir(hook_repo.work_dir())
+        .output()?;
+    let commit = String::from_utf8(output.stdout)?.trim().to_string();
+
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo_url}
+            rev: v1.0.0
+            hooks:
+              - id: hello
+          - repo: {repo_url}
+            rev: {commit}
+            hooks:
+              - id: hello
+                name: hello again
+    "});
+    context.git_add(".pre-commit-config.yaml");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hello....................................................................Passed
+    hello again..............................................................Passed
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(context.home_dir().child("repos").read_dir()?.count(), 1);
+    // Only the tag needs to be resolved, the commit is used as is.
+    assert_eq!(context.home_dir().child("refs").read_dir()?.count(), 1);
+
+    Ok(())
+}
+
+/// Repos cloned by older versions of prek are migrated instead of cloned again.
+#[test]
+fn migrate_legacy_repos() -> anyhow::Result<()> {
+    let hook_repo = TestContext::new();
+    let repo_url = hook_repo.init_hook_repo(HOOKS);
+
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo_url}
+            rev: v1.0.0
+            hooks:
+              - id: hello
+    "});
+    context.git_add(".pre-commit-config.yaml");
+
+    // A store without a version file, with a repo keyed by a hash of its URL and rev.
+    let legacy = context.home_dir().child("repos").child("0123456789abcdef");
+    Command::new("git")
+        .arg("clone")
+        .arg(&repo_url)
+        .arg(&*legacy)
+        .assert()
+        .success();
+    legacy
+        .child(".prek-repo.json")
+        .write_str(&format!(r#"{{"repo": "{repo_url}", "rev": "v1.0.0"}}"#))?;
+
+    // Offline, so the hook can only run from the migrated clone.
+    cmd_snapshot!(context.filters(), context.run().arg("--offline"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hello....................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    assert!(!legacy.exists());
+    assert_eq!(context.home_dir().child("repos").read_dir()?.count(), 1);
+    assert_eq!(
+        fs_err::read_to_string(context.home_dir().child(".version"))?,
+        "1"
+    );
+
+    Ok(())
+}
+
+/// Processes using the store don't block each other, only changes to the store layout do.
+#[test]
+fn shared_store_lock() {
+    let hook_repo = TestContext::new();
+    let repo_url = hook_repo.init_hook_repo(HOOKS);
+
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo_url}
+            rev: v1.0.0
+            hooks:
+              - id: hello
+    "});
+    context.git_add(".pre-commit-config.yaml");
+
+    // Another process using the store.
+    let _lock = context.lock_store_shared();
+
+    assert_cmd::Command::from_std(context.run())
+        .timeout(Duration::from_secs(60))
+        .assert()
+        .success();
+}
+
+/// Environments are built in a staging directory, partial ones left by interrupted installs
+/// are removed once no other process uses the store.
+#[test]
+fn partial_envs_removed() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_node_hook_config();
+    context.git_add(".pre-commit-config.yaml");
+
+    let partial = context
+        .home_dir()
+        .child("hooks")
+        .child(".tmp")
+        .child("node-partial");
+    partial.child("bin").child("node").write_str("")?;
+
+    // Another process using the store may be installing it.
+    let lock = context.lock_store_shared();
+    assert_cmd::Command::from_std(context.run())
+        .timeout(Duration::from_secs(60))
+        .assert()
+        .success();
+    assert!(partial.exists());
+
+    lock.file().unlock()?;
+    context.run().assert().success();
+    assert!(!partial.exists());
+
+    // The environment was moved out of the staging directory.
+    let envs = fs_err::read_dir(context.home_dir().child("hooks").path())?
+        .map(|entry| entry.map(|entry| entry.file_name()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(envs.len(), 2);
+    assert!(
+        envs.iter()
+            .any(|name| name.to_string_lossy().starts_with("node-"))
+    );
+    assert_eq!(
+        context
+            .home_dir()
+            .child("hooks")
+            .child(".tmp")
+            .read_dir()?
+            .count(),
+        0
+    );
+
+    Ok(())
+}
+
+/// Paths to the staging directory written by installers are rewritten when the environment
+/// is moved into place.
+#[cfg(unix)]
+#[test]
+fn staged_env_relocated() {
+    let context = TestContext::new();
+    context.init_project();
+    let mirror = context.node_mirror();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - {GREETER_HOOK}
+    "});
+    context.git_add(".pre-commit-config.yaml");
+
+    cmd_snapshot!(context.filters(), context.run().env(EnvVars::PREK_NODE_MIRROR, &mirror), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    greeter..................................................................Passed
+    - hook id: greeter
+    - duration: [TIME]
+      greeter says hello
+
+    ----- stderr -----
+    ");
+}
+
+/// Run the command while the store lock with the given prefix is held by another process,
+/// and check that it waits for the lock.
+fn assert_waits_for_lock(
+    context: &TestContext,
+    mut command: Command,
+    prefix: &str,
+    resource: &str,
+) -> anyhow::Result<()> {
+    let lock = fs_err::read_dir(context.home_dir().child("locks").path())?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .find(|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with(prefix))
+        })
+        .expect("lock file exists");
+    let lock = fs_err::File::open(lock)?;
+    lock.file().lock()?;
+
+    let child = command
+        .arg("-vv")
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()?;
+    std::thread::sleep(Duration::from_secs(2));
+    lock.file().unlock()?;
+
+    let output = child.wait_with_output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert!(
+        stderr
+            .lines()
+            .any(|line| line.contains("Waiting to acquire lock") && line.contains(resource)),
+        "{stderr}"
+    );
+
+    Ok(())
+}
+
+/// Cloning a repo waits for another process cloning the same repo.
+#[test]
+fn repo_lock() -> anyhow::Result<()> {
+    let hook_repo = TestContext::new();
+    let repo_url = hook_repo.init_hook_repo(HOOKS);
+
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo_url}
+            rev: v1.0.0
+            hooks:
+              - id: hello
+    "});
+    context.git_add(".pre-commit-config.yaml");
+    context.run().assert().success();
+
+    // Clone the repo again.
+    fs_err::remove_dir_all(context.home_dir().child("repos").path())?;
+    fs_err::remove_dir_all(context.home_dir().child("refs").path())?;
+
+    assert_waits_for_lock(
+        &context,
+        context.run(),
+        "repo-",
+        &format!("repo `{repo_url}@v1.0.0`"),
+    )
+}
+
+/// Installing an environment waits for another process installing one with the same
+/// language and dependencies.
+#[test]
+fn env_lock() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_node_hook_config();
+    context.git_add(".pre-commit-config.yaml");
+    context.run().assert().success();
+
+    // Install the environment again.
+    fs_err::remove_dir_all(context.home_dir().child("hooks").path())?;
+
+    assert_waits_for_lock(
+        &context,
+        context.run(),
+        "env-",
+        "node environment of `node`",
+    )
+}
@@ -44,7 +44,7 @@ fn validate_config() -> anyhow::Result<()> {
         "})?;
 
     // Validate multiple files.
-    cmd_snapshot!(context.filters(), context.validate_config().arg(".pre-commit-config.yaml").arg("config-1.yaml"), @r#"
+    cmd_snapshot!(context.filters(), context.validate_config().arg(".pre-commit-config.yaml").arg("config-1.yaml"), @r"
     success: false
     exit_code: 1
     ----- stdout -----
@@ -52,7 +52,14 @@ fn validate_config() -> anyhow::Result<()> {
     ----- stderr -----
     error: Failed to parse `config-1.yaml`
       caused by: repos: Invalid remote repo: missing field `rev` at line 2 column 3
-    "#);
+
+       ╭─[config-1.yaml:2:3]
+     1 │ repos:
+     2 │   - repo: https://github.com/pre-commit/pre-commit-hooks
+       ·   ┬
+       ·   ╰── repos: Invalid remote repo: missing field `rev`
+       ╰────
+    ");
 
     Ok(())
 }
@@ -104,7 +111,7 @@ fn validate_manifest() -> anyhow::Result<()> {
         "})?;
 
     // Validate multiple files.
-    cmd_snapshot!(context.filters(), context.validate_manifest().arg(".pre-commit-hooks.yaml").arg("hooks-1.yaml"), @r#"
+    cmd_snapshot!(context.filters(), context.validate_manifest().arg(".pre-commit-hooks.yaml").arg("hooks-1.yaml"), @r"
     success: false
     exit_code: 1
     ----- stdout -----
@@ -112,7 +119,361 @@ fn validate_manifest() -> anyhow::Result<()> {
     ----- stderr -----
     error: Failed to parse `hooks-1.yaml`
       caused by: .[0]: missing field `entry` at line 1 column 5
+
+       ╭─[hooks-1.yaml:1:5]
+     1 │ -   id: check-added-large-files
+       ·     ─┬─
+       ·      ╰── .[0]: missing field `entry`
+     2 │     name: check for added large files
+       ╰────
+    ");
+
+    Ok(())
+}
+
+/// Warn about unknown keys and deprecated stages, which are errors with `--strict`.
+#[test]
+fn validate_config_strict() {
+    let context = TestContext::new();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        default_stages: [commit]
+        fail_fsat: true
+        repos:
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+            rev: v5.0.0
+            revision: v5.0.0
+            hooks:
+              - id: trailing-whitespace
+                exclude_type: [markdown]
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                entry: echo
+                language: system
+                stage: [push]
+                stages: [push]
+    "});
+
+    cmd_snapshot!(context.filters(), context.validate_config().arg(".pre-commit-config.yaml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Unknown key `fail_fsat` in `.pre-commit-config.yaml`, did you mean `fail_fast`?
+    warning: Stage `commit` in `default_stages` of `.pre-commit-config.yaml` is deprecated, use `pre-commit` instead
+    warning: Unknown key `revision` in repo `https://github.com/pre-commit/pre-commit-hooks` of `.pre-commit-config.yaml`, did you mean `rev`?
+    warning: Unknown key `exclude_type` in hook `trailing-whitespace` of `.pre-commit-config.yaml`, did you mean `exclude_types`?
+    warning: Unknown key `stage` in hook `echo` of `.pre-commit-config.yaml`, did you mean `stages`?
+    warning: Stage `push` in hook `echo` of `.pre-commit-config.yaml` is deprecated, use `pre-push` instead
+    ");
+
+    cmd_snapshot!(context.filters(), context.validate_config().arg(".pre-commit-config.yaml").arg("--strict"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unknown key `fail_fsat` in `.pre-commit-config.yaml`, did you mean `fail_fast`?
+    error: Stage `commit` in `default_stages` of `.pre-commit-config.yaml` is deprecated, use `pre-commit` instead
+    error: Unknown key `revision` in repo `https://github.com/pre-commit/pre-commit-hooks` of `.pre-commit-config.yaml`, did you mean `rev`?
+    error: Unknown key `exclude_type` in hook `trailing-whitespace` of `.pre-commit-config.yaml`, did you mean `exclude_types`?
+    error: Unknown key `stage` in hook `echo` of `.pre-commit-config.yaml`, did you mean `stages`?
+    error: Stage `push` in hook `echo` of `.pre-commit-config.yaml` is deprecated, use `pre-push` instead
+    ");
+}
+
+/// Configs and hooks requiring a newer prek or pre-commit are rejected.
+#[test]
+fn minimum_version() {
+    let context = TestContext::new();
+    context.init_project();
+
+    let version = regex::escape(env!("CARGO_PKG_VERSION"));
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([(version.as_str(), "[VERSION]")])
+        .collect();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        minimum_pre_commit_version: '2.9.2'
+        minimum_prek_version: '0.0.1'
+        repos: []
+    "});
+    cmd_snapshot!(context.filters(), context.validate_config().arg(".pre-commit-config.yaml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        minimum_pre_commit_version: '99.0'
+        repos: []
+    "});
+    cmd_snapshot!(context.filters(), context.validate_config().arg(".pre-commit-config.yaml"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Config `.pre-commit-config.yaml` is not supported by this version of prek
+      caused by: Required minimum pre-commit version `99.0` is greater than the version prek is compatible with (`4.2.0`), please consider updating prek by running `prek self update`
+
+       ╭─[.pre-commit-config.yaml:1:1]
+     1 │ minimum_pre_commit_version: '99.0'
+       · ─────────────┬────────────
+       ·              ╰── required here
+     2 │ repos: []
+       ╰────
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        minimum_prek_version: '99.0.0'
+        repos: []
+    "});
+    cmd_snapshot!(filters.clone(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Config `.pre-commit-config.yaml` is not supported by this version of prek
+      caused by: Required minimum prek version `99.0.0` is greater than current version `[VERSION]`, please consider updating prek by running `prek self update`
+
+       ╭─[.pre-commit-config.yaml:1:1]
+     1 │ minimum_prek_version: '99.0.0'
+       · ──────────┬─────────
+       ·           ╰── required here
+     2 │ repos: []
+       ╰────
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                entry: echo
+                language: system
+                minimum_prek_version: 'latest'
+    "});
+    context.git_add(".");
+    cmd_snapshot!(filters.clone(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Hook `echo` is invalid
+      caused by: Invalid `minimum_prek_version` `latest`
+
+       ╭─[.pre-commit-config.yaml:8:9]
+     7 │         language: system
+     8 │         minimum_prek_version: 'latest'
+       ·         ──────────┬─────────
+       ·                   ╰── invalid version
+       ╰────
+    ");
+}
+
+/// Snippets point to the hook in flow-style YAML, inline TOML tables and base configs.
+#[test]
+fn snippet_locations() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    let cwd = context.work_dir();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos: [{repo: local, hooks: [{id: echo, name: echo, entry: echo, language: system, minimum_prek_version: latest}]}]
+    "});
+    context.git_add(".");
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Hook `echo` is invalid
+      caused by: Invalid `minimum_prek_version` `latest`
+
+       ╭─[.pre-commit-config.yaml:1:85]
+     1 │ repos: [{repo: local, hooks: [{id: echo, name: echo, entry: echo, language: system, minimum_prek_version: latest}]}]
+       ·                                                                                     ──────────┬─────────
+       ·                                                                                               ╰── invalid version
+       ╰────
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        extends: [base.yaml]
+        repos: []
+    "});
+    cwd.child("base.yaml").write_str(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                entry: echo
+                language: system
+              - id: broken
+                name: broken
+                entry: echo
+                language: system
+                minimum_prek_version: latest
+    "})?;
+    context.git_add(".");
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Hook `broken` is invalid
+      caused by: Invalid `minimum_prek_version` `latest`
+
+        ╭─[base.yaml:12:9]
+     11 │         language: system
+     12 │         minimum_prek_version: latest
+        ·         ──────────┬─────────
+        ·                   ╰── invalid version
+        ╰────
+    ");
+
+    fs_err::remove_file(cwd.child(".pre-commit-config.yaml"))?;
+    cwd.child("prek.toml").write_str(indoc::indoc! {r#"
+        [[repos]]
+        repo = "local"
+        hooks = [
+          { id = "echo", name = "echo", entry = "echo", language = "system" },
+          { id = "broken", name = "broken", entry = "echo", language = "system", minimum_prek_version = "latest" },
+        ]
+    "#})?;
+    context.git_add(".");
+    cmd_snapshot!(context.filters(), context.run(), @r#"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Hook `broken` is invalid
+      caused by: Invalid `minimum_prek_version` `latest`
+
+       ╭─[prek.toml:5:74]
+     4 │   { id = "echo", name = "echo", entry = "echo", language = "system" },
+     5 │   { id = "broken", name = "broken", entry = "echo", language = "system", minimum_prek_version = "latest" },
+       ·                                                                          ──────────┬─────────
+       ·                                                                                    ╰── invalid version
+     6 │ ]
+       ╰────
     "#);
 
     Ok(())
 }
+
+#[test]
+fn validate_config_regex() {
+    let context = TestContext::new();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        exclude: '^(vendor/.*)+$'
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                entry: echo
+                language: system
+                files: '(foo'
+    "});
+    cmd_snapshot!(context.filters(), context.validate_config().arg(".pre-commit-config.yaml"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Invalid file pattern in `.pre-commit-config.yaml`
+      caused by: `files` of hook `echo` is not a valid regex: Parsing error at position 4: Opening parenthesis without closing parenthesis
+
+       ╭─[.pre-commit-config.yaml:9:9]
+     8 │         language: system
+     9 │         files: '(foo'
+       ·         ──┬──
+       ·           ╰── Parsing error at position 4: Opening parenthesis without closing parenthesis
+       ╰────
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        exclude: '^(vendor/.*)+$'
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                entry: echo
+                language: system
+    "});
+    cmd_snapshot!(context.filters(), context.validate_config().arg(".pre-commit-config.yaml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Regex `^(vendor/.*)+$` in `exclude` of `.pre-commit-config.yaml` has nested quantifiers, which may backtrack catastrophically
+    ");
+    cmd_snapshot!(context.filters(), context.validate_config().arg("--strict").arg(".pre-commit-config.yaml"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Regex `^(vendor/.*)+$` in `exclude` of `.pre-commit-config.yaml` has nested quantifiers, which may backtrack catastrophically
+    ");
+}
+
+#[test]
+fn validate_config_check_remote() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                entry: echo
+                language: system
+                additional_dependencies: ['foo']
+    "});
+
+    // Hooks are only built with `--check-remote`.
+    cmd_snapshot!(context.filters(), context.validate_config().arg(".pre-commit-config.yaml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+    cmd_snapshot!(context.filters(), context.validate_config().arg("--check-remote").arg(".pre-commit-config.yaml"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Hook `echo` is invalid
+      caused by: Hook specified `additional_dependencies` `foo` but the language `system` does not support installing dependencies for now
+
+       ╭─[.pre-commit-config.yaml:8:9]
+     7 │         language: system
+     8 │         additional_dependencies: ['foo']
+       ·         ───────────┬───────────
+       ·                    ╰── `system` does not support installing dependencies
+       ╰────
+    ");
+}